@@ -75,7 +75,9 @@ use lazy_static::lazy_static;
 use sp_core::ExecutionContext;
 
 use crate::worker::UntilImportedOrTimeout;
-use sp_consensus_poscan::{Difficulty, DifficultyApi, MAX_MINING_OBJ_LEN, POSCAN_ALGO_GRID2D};
+use sp_consensus_poscan::{
+	Difficulty, DifficultyApi, MAX_MINING_OBJ_LEN, POSCAN_ALGO_GRID2D, SEALED_OBJECT_INHERENT_IDENTIFIER,
+};
 
 lazy_static! {
     pub static ref CACHE: Mutex<BTreeSet<u64>> = {
@@ -318,6 +320,7 @@ impl<B, I, C, S, Algorithm, CAW, CIDP> PowBlockImport<B, I, C, S, Algorithm, CAW
 		block_id: BlockId<B>,
 		inherent_data_providers: CIDP::InherentDataProviders,
 		execution_context: ExecutionContext,
+		sealed_hashes: &[H256],
 	) -> Result<(), Error<B>> {
 		if *block.header().number() < self.check_inherents_after {
 			return Ok(())
@@ -333,9 +336,12 @@ impl<B, I, C, S, Algorithm, CAW, CIDP> PowBlockImport<B, I, C, S, Algorithm, CAW
 			return Ok(())
 		}
 
-		let inherent_data = inherent_data_providers
+		let mut inherent_data = inherent_data_providers
 			.create_inherent_data()
 			.map_err(|e| Error::CreateInherents(e))?;
+		inherent_data
+			.put_data(SEALED_OBJECT_INHERENT_IDENTIFIER, &sealed_hashes.to_vec())
+			.map_err(|e| Error::CreateInherents(e))?;
 
 		let inherent_res = self
 			.client
@@ -479,25 +485,6 @@ impl<B, I, C, S, Algorithm, CAW, CIDP> BlockImport<B> for PowBlockImport<B, I, C
 		let best_aux = PowAux::read::<_, B>(self.client.as_ref(), &best_hash)?;
 		let mut aux = PowAux::read::<_, B>(self.client.as_ref(), &parent_hash)?;
 
-		if let Some(inner_body) = block.body.take() {
-			// let timestamp_now = inherent_data.timestamp_inherent_data().map_err(|e| e.into_string())?;
-
-			let check_block = B::new(block.header.clone(), inner_body);
-
-			self.check_inherents(
-				check_block.clone(),
-				BlockId::Hash(parent_hash),
-				self.create_inherent_data_providers
-					.create_inherent_data_providers(parent_hash, ())
-					.await?,
-				block.origin.into(),
-				// timestamp_now,
-			)
-				.await?;
-
-			block.body = Some(check_block.deconstruct().1);
-		}
-
 		let digest_size = block.post_digests.len();
 
 		let pre_digest: Vec<u8> = find_pre_digest::<B>(&block.header)?.unwrap();
@@ -518,6 +505,26 @@ impl<B, I, C, S, Algorithm, CAW, CIDP> BlockImport<B> for PowBlockImport<B, I, C
 
 		let psdata = PoscanData{ alg_id: POSCAN_ALGO_GRID2D, hashes: hs.clone(), obj: pscan_obj };
 
+		if let Some(inner_body) = block.body.take() {
+			// let timestamp_now = inherent_data.timestamp_inherent_data().map_err(|e| e.into_string())?;
+
+			let check_block = B::new(block.header.clone(), inner_body);
+
+			self.check_inherents(
+				check_block.clone(),
+				BlockId::Hash(parent_hash),
+				self.create_inherent_data_providers
+					.create_inherent_data_providers(parent_hash, ())
+					.await?,
+				block.origin.into(),
+				&hs,
+				// timestamp_now,
+			)
+				.await?;
+
+			block.body = Some(check_block.deconstruct().1);
+		}
+
 		let intermediate = block.take_intermediate::<PowIntermediate::<Algorithm::Difficulty>>(
 			INTERMEDIATE_KEY
 		)?;
@@ -800,6 +807,50 @@ fn find_pre_digest<B: BlockT>(header: &B::Header) -> Result<Option<Vec<u8>>, Err
 	Ok(pre_digest)
 }
 
+/// PoScan items carried by an imported header.
+pub struct SealedHeader<B: BlockT> {
+	/// Header the seal was computed over.
+	pub pre_header: B::Header,
+	/// Encoded author from the pre-runtime digest.
+	pub pre_digest: Vec<u8>,
+	/// Raw seal.
+	pub seal: Seal,
+	/// Object hashes and (compressed) mined object.
+	pub poscan_data: PoscanData,
+}
+
+/// Split an imported header into the header the seal was computed over and the PoScan
+/// post-digests (seal, object hashes and mined object) appended to it on import.
+pub fn split_sealed_header<B: BlockT>(header: &B::Header) -> Result<SealedHeader<B>, Error<B>> {
+	let hash = header.hash();
+	let mut pre_header = header.clone();
+
+	let pscan_obj = fetch_seal::<B>(pre_header.digest_mut().pop().as_ref(), hash)?;
+	let pscan_hashes = fetch_seal::<B>(pre_header.digest_mut().pop().as_ref(), hash)?;
+	let seal = fetch_seal::<B>(pre_header.digest_mut().pop().as_ref(), hash)?;
+
+	if pscan_hashes.len() < 16 + 32 {
+		return Err(Error::HeaderUnsealed(hash))
+	}
+	let mut alg_id = [0u8; 16];
+	alg_id.copy_from_slice(&pscan_hashes[0..16]);
+	let hashes: Vec<H256> = pscan_hashes[16..].chunks(32).map(|h| H256::from_slice(h)).collect();
+
+	let pre_digest = find_pre_digest::<B>(&pre_header)?.ok_or(Error::HeaderUnsealed(hash))?;
+
+	// Same as in mining worker: pre-hash is computed without the GRANDPA digest.
+	if pre_header.digest().logs().len() == 2 {
+		let _ = pre_header.digest_mut().pop();
+	}
+
+	Ok(SealedHeader {
+		pre_header,
+		pre_digest,
+		seal,
+		poscan_data: PoscanData { alg_id, hashes, obj: pscan_obj },
+	})
+}
+
 /// Fetch PoW seal.
 fn fetch_seal<B: BlockT>(
 	digest: Option<&DigestItem>,
@@ -855,12 +906,63 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, L, CIDP, CAW>(
 	client: Arc<C>,
 	select_chain: S,
 	algorithm: Algorithm,
+	env: E,
+	sync_oracle: SO,
+	justification_sync_link: L,
+	pre_runtime: Option<Vec<u8>>,
+	create_inherent_data_providers: CIDP,
+	timeout: Duration,
+	build_time: Duration,
+	can_author_with: CAW,
+) -> (
+	MiningHandle<Block, Algorithm, C, L, <E::Proposer as Proposer<Block>>::Proof>,
+	impl Future<Output = ()>,
+)
+	where
+		Block: BlockT,
+		C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + 'static + BlockBackend<Block> + HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>,
+		S: SelectChain<Block> + 'static,
+		Algorithm: PowAlgorithm<Block> + Clone,
+		Algorithm::Difficulty: Send + 'static,
+		E: Environment<Block> + Send + Sync + 'static,
+		E::Error: std::fmt::Debug,
+		E::Proposer: Proposer<Block, Transaction = sp_api::TransactionFor<C, Block>>,
+		SO: SyncOracle + Clone + Send + Sync + 'static,
+		L: sc_consensus::JustificationSyncLink<Block>,
+		CIDP: CreateInherentDataProviders<Block, ()>,
+		CAW: CanAuthorWith<Block> + Clone + Send + 'static,
+{
+	let timer = UntilImportedOrTimeout::new(client.import_notification_stream(), timeout);
+
+	start_mining_worker_with_trigger(
+		block_import,
+		client,
+		select_chain,
+		algorithm,
+		env,
+		sync_oracle,
+		justification_sync_link,
+		pre_runtime,
+		create_inherent_data_providers,
+		timer,
+		build_time,
+		can_author_with,
+	)
+}
+
+/// Same as `start_mining_worker`, but a new block is proposed each time `trigger` yields instead
+/// of on block import or timeout. This allows blocks to be built on demand, e.g. for dev sealing.
+pub fn start_mining_worker_with_trigger<Block, C, S, Algorithm, E, SO, L, CIDP, CAW, T>(
+	block_import: BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>,
+	_client: Arc<C>,
+	select_chain: S,
+	algorithm: Algorithm,
 	mut env: E,
 	mut sync_oracle: SO,
 	justification_sync_link: L,
 	pre_runtime: Option<Vec<u8>>,
 	create_inherent_data_providers: CIDP,
-	timeout: Duration,
+	mut trigger: T,
 	build_time: Duration,
 	can_author_with: CAW,
 ) -> (
@@ -880,14 +982,14 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, L, CIDP, CAW>(
 		L: sc_consensus::JustificationSyncLink<Block>,
 		CIDP: CreateInherentDataProviders<Block, ()>,
 		CAW: CanAuthorWith<Block> + Clone + Send + 'static,
+		T: Stream<Item = ()> + Unpin,
 {
-	let mut timer = UntilImportedOrTimeout::new(client.import_notification_stream(), timeout);
 	let worker = MiningHandle::new(algorithm.clone(), block_import, justification_sync_link);
 	let worker_ret = worker.clone();
 
 	let task = async move {
 		loop {
-			if timer.next().await.is_none() {
+			if trigger.next().await.is_none() {
 				break;
 			}
 
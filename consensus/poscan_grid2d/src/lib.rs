@@ -4,7 +4,7 @@ use sc_consensus_poscan::{Error, PoscanData, PowAlgorithm};
 use sha3::{Digest, Sha3_256};
 use sp_api::ProvideRuntimeApi;
 use sp_consensus_poscan::Seal as RawSeal;
-use sp_consensus_poscan::{DifficultyApi, decompress_obj};
+use sp_consensus_poscan::{DifficultyApi, try_decompress_obj, POSCAN_GRID2D_DEPTH, POSCAN_GRID2D_GRID_SIZE};
 use sp_core::{H256, U256, crypto::Pair, hashing::blake2_256, ByteArray};
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::Block as BlockT;
@@ -154,77 +154,116 @@ where
 		difficulty: Self::Difficulty,
 		poscan_data: &PoscanData,
 	) -> Result<bool, Error<B>> {
-		// Try to construct a seal object by decoding the raw seal given
-		let seal = match Seal::decode(&mut &seal[..]) {
-			Ok(seal) => seal,
-			Err(_) => {
-				info!(">>> verify: no seal");
-				return Ok(false)
-			},
-		};
+		Ok(check_seal(parent, pre_hash, pre_digest, seal, difficulty, poscan_data).is_ok())
+	}
+}
 
-		// See whether the hash meets the difficulty requirement. If not, fail fast.
-		if !hash_meets_difficulty(&seal.work, difficulty) {
-			info!(">>> verify: hash_meets_difficulty - false");
-			info!(">>> work:{} poscan_hash:{} difficulty: {}", &seal.work, &seal.poscan_hash, difficulty);
-			return Ok(false);
-		}
+/// Step of the seal verification that failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerifyFailure {
+	/// The raw seal could not be decoded.
+	SealDecode,
+	/// The seal work does not meet the difficulty.
+	Difficulty,
+	/// The seal does not match the work recomputed from the pre-hash.
+	WorkMismatch,
+	/// There is no pre-runtime digest.
+	NoPreDigest,
+	/// The pre-runtime digest is not a valid author key.
+	AuthorDecode,
+	/// The miner signature is invalid.
+	Signature,
+	/// The mined object could not be decompressed.
+	Decompress,
+	/// The object hashes do not match the rehashed object.
+	ObjectHashes,
+}
 
-		// Make sure the provided work actually comes from the correct pre_hash
-		let compute = Compute {
-			difficulty,
-			pre_hash: *pre_hash,
-			poscan_hash: seal.poscan_hash,
-		};
+/// Run all the checks of `PoscanAlgorithm::verify` and report the first one that failed.
+pub fn check_seal(
+	parent: &H256,
+	pre_hash: &H256,
+	pre_digest: Option<&[u8]>,
+	seal: &RawSeal,
+	difficulty: U256,
+	poscan_data: &PoscanData,
+) -> Result<(), VerifyFailure> {
+	// Try to construct a seal object by decoding the raw seal given
+	let seal = match Seal::decode(&mut &seal[..]) {
+		Ok(seal) => seal,
+		Err(_) => {
+			info!(">>> verify: no seal");
+			return Err(VerifyFailure::SealDecode)
+		},
+	};
 
-		if compute.seal(seal.signature.clone()) != seal {
-			info!(">>> verify: compute.compute() != seal");
-			return Ok(false);
-		}
+	// See whether the hash meets the difficulty requirement. If not, fail fast.
+	if !hash_meets_difficulty(&seal.work, difficulty) {
+		info!(">>> verify: hash_meets_difficulty - false");
+		info!(">>> work:{} poscan_hash:{} difficulty: {}", &seal.work, &seal.poscan_hash, difficulty);
+		return Err(VerifyFailure::Difficulty);
+	}
 
-		let pre_digest = match pre_digest {
-			Some(pre_digest) => pre_digest,
-			None => {
-				info!(">>> verify: no pre_digest");
-				return Ok(false)
-			},
-		};
+	// Make sure the provided work actually comes from the correct pre_hash
+	let compute = Compute {
+		difficulty,
+		pre_hash: *pre_hash,
+		poscan_hash: seal.poscan_hash,
+	};
 
-		let author = match app::Public::decode(&mut &pre_digest[..]) {
-			Ok(author) => author,
-			Err(_) => {
-				info!(">>> verify: decode author failed");
-				return Ok(false)
-			},
-		};
+	if compute.seal(seal.signature.clone()) != seal {
+		info!(">>> verify: compute.compute() != seal");
+		return Err(VerifyFailure::WorkMismatch);
+	}
 
+	let pre_digest = match pre_digest {
+		Some(pre_digest) => pre_digest,
+		None => {
+			info!(">>> verify: no pre_digest");
+			return Err(VerifyFailure::NoPreDigest)
+		},
+	};
 
-		if !compute.verify(&seal.signature, &author) {
-			// use sp_core::Public;
-			info!(">>> pre_hash: {:x?}", &compute.pre_hash);
-			info!(">>> seal.difficulty: {}", &seal.difficulty);
-			info!(">>> seal.work: {}", &seal.work);
-			info!(">>> seal.poscan_hash: {}", &seal.poscan_hash);
-			info!(">>> seal signature is {:x?}", &seal.signature.to_vec());
+	let author = match app::Public::decode(&mut &pre_digest[..]) {
+		Ok(author) => author,
+		Err(_) => {
+			info!(">>> verify: decode author failed");
+			return Err(VerifyFailure::AuthorDecode)
+		},
+	};
 
-			info!(">>> verify: miner signature is invalid");
-			info!(">>> verify: miner author is {:x?}", &author.to_raw_vec());
-			return Ok(false)
-		}
 
-		let mut obj = poscan_data.obj.clone();
-		if poscan_data.obj[..4] == vec![b'l', b'z', b's', b's'] {
-			obj = decompress_obj(&obj[4..]);
-		}
+	if !compute.verify(&seal.signature, &author) {
+		// use sp_core::Public;
+		info!(">>> pre_hash: {:x?}", &compute.pre_hash);
+		info!(">>> seal.difficulty: {}", &seal.difficulty);
+		info!(">>> seal.work: {}", &seal.work);
+		info!(">>> seal.poscan_hash: {}", &seal.poscan_hash);
+		info!(">>> seal signature is {:x?}", &seal.signature.to_vec());
 
-		let hashes = get_obj_hashes(&obj, parent);
-		if hashes != poscan_data.hashes {
-			info!(">>> verify: hashes != poscan_data.hashes");
-			return Ok(false)
-		}
+		info!(">>> verify: miner signature is invalid");
+		info!(">>> verify: miner author is {:x?}", &author.to_raw_vec());
+		return Err(VerifyFailure::Signature)
+	}
 
-		Ok(true)
+	let mut obj = poscan_data.obj.clone();
+	if poscan_data.obj.starts_with(b"lzss") {
+		obj = match try_decompress_obj(&obj[4..]) {
+			Some(obj) => obj,
+			None => {
+				info!(">>> verify: decompress object failed");
+				return Err(VerifyFailure::Decompress)
+			},
+		};
 	}
+
+	let hashes = get_obj_hashes(&obj, parent);
+	if hashes != poscan_data.hashes {
+		info!(">>> verify: hashes != poscan_data.hashes");
+		return Err(VerifyFailure::ObjectHashes)
+	}
+
+	Ok(())
 }
 
 
@@ -237,7 +276,13 @@ pub fn get_obj_hashes(data: &Vec<u8>, pre: &H256) -> Vec<H256> {
 	let mut buf: Vec<H256> = Vec::new();
 
 	let pre = pre.encode()[0..4].try_into().ok();
-	let res = p3d::p3d_process(data, p3d::AlgoType::Grid2d, 8, 66, pre);
+	let res = p3d::p3d_process(
+		data,
+		p3d::AlgoType::Grid2d,
+		POSCAN_GRID2D_DEPTH as _,
+		POSCAN_GRID2D_GRID_SIZE as _,
+		pre,
+	);
 
 	match res {
 		Ok(v) => {
@@ -16,6 +16,18 @@ pub struct Cli {
 
 	#[clap(long)]
 	pub threads: Option<usize>,
+
+	/// Seal blocks of a development chain with a built-in object instead of mining.
+	#[clap(long, arg_enum)]
+	pub sealing: Option<Sealing>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal a block on each `engine_createBlock` RPC call.
+	Manual,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -45,11 +57,39 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Sub-commands concerned with benchmarking.
+	#[clap(subcommand)]
+	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state, e.g. rehearse a runtime upgrade on a snapshot.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
+
 	#[clap(name = "import-mining-key")]
 	ImportMiningKey(ImportMiningKeyCommand),
 
 	#[clap(name = "generate-mining-key")]
 	GenerateMiningKey(GenerateMiningKeyCommand),
+
+	/// Re-verify PoScan seals and objects of stored blocks.
+	#[clap(name = "verify-chain")]
+	VerifyChain(VerifyChainCommand),
+
+	/// Export mined 3D objects of stored blocks as .obj files with a manifest.
+	#[clap(name = "export-objects")]
+	ExportObjects(ExportObjectsCommand),
+
+	/// Replay the difficulty retarget over a hashrate scenario or recorded timestamps.
+	#[clap(name = "simulate-difficulty")]
+	SimulateDifficulty(SimulateDifficultyCommand),
+
+	/// Project total supply, reward split and locked balance over a block range.
+	#[clap(name = "simulate-emission")]
+	SimulateEmission(SimulateEmissionCommand),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -86,3 +126,189 @@ impl sc_cli::CliConfiguration for GenerateMiningKeyCommand {
 	fn shared_params(&self) -> &sc_cli::SharedParams { &self.shared_params }
 	fn keystore_params(&self) -> Option<&sc_cli::KeystoreParams> { Some(&self.keystore_params) }
 }
+
+#[derive(Debug, clap::Parser)]
+pub struct VerifyChainCommand {
+	/// First block to verify.
+	#[clap(long, default_value = "1")]
+	pub from: u32,
+
+	/// Last block to verify. Defaults to the best block.
+	#[clap(long)]
+	pub to: Option<u32>,
+
+	/// Write the JSON report to this file instead of stdout.
+	#[clap(long, parse(from_os_str))]
+	pub output: Option<std::path::PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: sc_cli::ImportParams,
+}
+
+impl sc_cli::CliConfiguration for VerifyChainCommand {
+	fn shared_params(&self) -> &sc_cli::SharedParams { &self.shared_params }
+	fn import_params(&self) -> Option<&sc_cli::ImportParams> { Some(&self.import_params) }
+}
+
+#[derive(Debug, Clone, Copy, clap::ArgEnum)]
+pub enum OutputFormat {
+	Csv,
+	Json,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportObjectsCommand {
+	/// Directory to write the objects and the manifest to.
+	#[clap(parse(from_os_str))]
+	pub output_dir: std::path::PathBuf,
+
+	/// First block to export.
+	#[clap(long, default_value = "1")]
+	pub from: u32,
+
+	/// Last block to export. Defaults to the best block.
+	#[clap(long)]
+	pub to: Option<u32>,
+
+	/// Manifest file format.
+	#[clap(long, arg_enum, default_value = "csv")]
+	pub manifest: OutputFormat,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: sc_cli::ImportParams,
+}
+
+impl sc_cli::CliConfiguration for ExportObjectsCommand {
+	fn shared_params(&self) -> &sc_cli::SharedParams { &self.shared_params }
+	fn import_params(&self) -> Option<&sc_cli::ImportParams> { Some(&self.import_params) }
+}
+
+/// A constant hashrate, in hashes per second, held for a number of blocks.
+#[derive(Debug, Clone, Copy)]
+pub struct HashrateStep {
+	pub blocks: u32,
+	pub hashrate: f64,
+}
+
+impl std::str::FromStr for HashrateStep {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (blocks, hashrate) = s.split_once(':')
+			.ok_or_else(|| format!("Expected <blocks>:<hashrate>, got {}", s))?;
+		let blocks = blocks.parse().map_err(|e| format!("Invalid block count {}: {}", blocks, e))?;
+		let hashrate: f64 = hashrate.parse().map_err(|e| format!("Invalid hashrate {}: {}", hashrate, e))?;
+		if !(hashrate > 0.0) {
+			return Err(format!("Hashrate must be positive, got {}", hashrate))
+		}
+		Ok(Self { blocks, hashrate })
+	}
+}
+
+/// Difficulty adjustment algorithms of the difficulty pallet.
+#[derive(Debug, Clone, Copy, clap::ArgEnum)]
+pub enum DifficultyAlgorithm {
+	/// Damped and clamped moving average, with the runtime's damp and clamp factors.
+	MovingAverage,
+	/// Linearly weighted moving average.
+	Lwma,
+	/// ASERT-style exponential adjustment.
+	Asert,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SimulateDifficultyCommand {
+	/// Hashrate scenario as comma separated <blocks>:<hashes per second> steps.
+	#[clap(long, value_delimiter = ',', required_unless_present = "timestamps", conflicts_with = "timestamps")]
+	pub hashrate: Vec<HashrateStep>,
+
+	/// CSV of block timestamps in milliseconds, e.g. an export-objects manifest.
+	#[clap(long, parse(from_os_str))]
+	pub timestamps: Option<std::path::PathBuf>,
+
+	/// Difficulty used for the empty slots of the window.
+	#[clap(long, default_value = "10")]
+	pub initial_difficulty: u128,
+
+	/// Target block time in milliseconds.
+	#[clap(long, default_value_t = sp_consensus_poscan::BLOCK_TIME)]
+	pub block_time: u64,
+
+	/// Number of blocks in the retarget window.
+	#[clap(long, default_value_t = sp_consensus_poscan::DIFFICULTY_ADJUST_WINDOW)]
+	pub window: u64,
+
+	/// Difficulty adjustment algorithm, as set with `DifficultyAdjustment` in the runtime.
+	#[clap(long, arg_enum, default_value = "moving-average")]
+	pub algorithm: DifficultyAlgorithm,
+
+	/// Half-life of the ASERT adjustment in blocks.
+	#[clap(long, default_value = "60")]
+	pub half_life: u32,
+
+	/// Lower difficulty bound set by governance. Defaults to `MIN_DIFFICULTY`.
+	#[clap(long)]
+	pub min_difficulty: Option<u128>,
+
+	/// Upper difficulty bound set by governance. Defaults to `MAX_DIFFICULTY`.
+	#[clap(long)]
+	pub max_difficulty: Option<u128>,
+
+	/// Output format of the series.
+	#[clap(long, arg_enum, default_value = "csv")]
+	pub format: OutputFormat,
+
+	/// Write the series to this file instead of stdout.
+	#[clap(long, parse(from_os_str))]
+	pub output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SimulateEmissionCommand {
+	/// First block of the projection.
+	#[clap(long, default_value = "1")]
+	pub from: u32,
+
+	/// Last block of the projection.
+	#[clap(long)]
+	pub to: u32,
+
+	/// Output a point every this many blocks.
+	#[clap(long, default_value = "1440")]
+	pub step: u32,
+
+	/// Number of validators sharing the validator part of the reward.
+	#[clap(long, default_value = "1")]
+	pub validators: u32,
+
+	/// Lock period in days, as chosen by an account, earning the lock bonus. Defaults to the
+	/// runtime default lock parameters, with no bonus.
+	#[clap(long, requires = "lock_divide")]
+	pub lock_period: Option<u16>,
+
+	/// Number of parts the lock period is divided into.
+	#[clap(long, requires = "lock_period")]
+	pub lock_divide: Option<u16>,
+
+	/// Supply at `from`, in the smallest unit.
+	#[clap(long, default_value = "0")]
+	pub initial_supply: u128,
+
+	/// Output format of the series.
+	#[clap(long, arg_enum, default_value = "csv")]
+	pub format: OutputFormat,
+
+	/// Write the series to this file instead of stdout.
+	#[clap(long, parse(from_os_str))]
+	pub output: Option<std::path::PathBuf>,
+}
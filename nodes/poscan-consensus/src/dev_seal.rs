@@ -0,0 +1,141 @@
+//! Instant and manual sealing for development chains.
+//!
+//! Instead of waiting for 3D objects pushed by an external miner, blocks are sealed with a
+//! built-in fixture object. Each attempt rotates the fixture, which yields new object hashes
+//! and so a new work hash, until the work meets the difficulty.
+
+use std::sync::Arc;
+use futures::{channel::mpsc, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use sc_client_api::BlockchainEvents;
+use sc_consensus_poscan::{MiningMetadata, PoscanData};
+use sp_consensus::BlockOrigin;
+use sp_consensus_poscan::POSCAN_ALGO_GRID2D;
+use sp_core::{Encode, H256, U256};
+use sp_runtime::traits::Block as BlockT;
+use poscan_grid2d::{get_obj_hashes, hash_meets_difficulty, Compute, DoubleHash};
+
+/// Object sealed into development blocks.
+const DEV_OBJECT: &str = include_str!("../res/dev_object.obj");
+
+/// Give up on a build after this many rotations of the fixture.
+const MAX_ATTEMPTS: u32 = 100_000;
+
+/// Secret URI of the key development blocks are sealed with.
+pub const DEV_SEALING_SURI: &str = "//Alice";
+
+/// Rotate the vertices of the fixture object by angles derived from `attempt`.
+fn rotated_object(attempt: u32) -> Vec<u8> {
+	// Irrational steps so that no two attempts use the same rotation.
+	let a = attempt as f64 * 0.618_033_988_75 * std::f64::consts::TAU;
+	let b = attempt as f64 * 0.414_213_562_37 * std::f64::consts::TAU;
+	let (sa, ca) = a.sin_cos();
+	let (sb, cb) = b.sin_cos();
+
+	let mut obj = String::with_capacity(DEV_OBJECT.len() * 2);
+	for line in DEV_OBJECT.lines() {
+		let coords: Vec<f64> = match line.strip_prefix("v ") {
+			Some(v) => v.split_whitespace().filter_map(|c| c.parse().ok()).collect(),
+			None => Vec::new(),
+		};
+		if coords.len() == 3 {
+			let (x, y, z) = (coords[0], coords[1], coords[2]);
+			// around z, then around x
+			let (x, y) = (x * ca - y * sa, x * sa + y * ca);
+			let (y, z) = (y * cb - z * sb, y * sb + z * cb);
+			obj.push_str(&format!("v {:.6} {:.6} {:.6}\n", x, y, z));
+		} else {
+			obj.push_str(line);
+			obj.push('\n');
+		}
+	}
+
+	obj.into_bytes()
+}
+
+/// Seal the current build with the fixture object. Returns `None` if no rotation met the
+/// difficulty within `MAX_ATTEMPTS`.
+pub fn seal_build(
+	metadata: &MiningMetadata<H256, U256>,
+	pair: &sc_consensus_poscan::app::Pair,
+) -> Option<(Vec<u8>, PoscanData)> {
+	for attempt in 0..MAX_ATTEMPTS {
+		let obj = rotated_object(attempt);
+		let hashes = get_obj_hashes(&obj, &metadata.best_hash);
+		if hashes.is_empty() {
+			continue
+		}
+
+		let dh = DoubleHash { pre_hash: metadata.pre_hash, obj_hash: hashes[0] };
+		let compute = Compute {
+			difficulty: metadata.difficulty,
+			pre_hash: metadata.pre_hash,
+			poscan_hash: dh.calc_hash(),
+		};
+		let seal = compute.seal(compute.sign(pair));
+		if hash_meets_difficulty(&seal.work, seal.difficulty) {
+			return Some((seal.encode(), PoscanData { alg_id: POSCAN_ALGO_GRID2D, hashes, obj }))
+		}
+	}
+
+	None
+}
+
+#[rpc(client, server)]
+pub trait DevSealingRpcApi<BlockHash> {
+	/// Seal a new block and return its hash once it is imported.
+	#[method(name = "engine_createBlock")]
+	async fn create_block(&self) -> RpcResult<BlockHash>;
+}
+
+pub struct DevSealingRpc<C, Block> {
+	client: Arc<C>,
+	command_sink: mpsc::UnboundedSender<()>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> DevSealingRpc<C, Block> {
+	pub fn new(client: Arc<C>, command_sink: mpsc::UnboundedSender<()>) -> Self {
+		Self {
+			client,
+			command_sink,
+			_marker: Default::default(),
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block> DevSealingRpcApiServer<<Block as BlockT>::Hash> for DevSealingRpc<C, Block>
+	where
+		Block: BlockT,
+		C: BlockchainEvents<Block> + Send + Sync + 'static,
+{
+	async fn create_block(&self) -> RpcResult<<Block as BlockT>::Hash> {
+		let mut imported = self.client.import_notification_stream();
+
+		self.command_sink.unbounded_send(())
+			.map_err(|_e|
+				JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+					ErrorCode::ServerError(1).code(),
+					format!("Sealing is not running"),
+					None::<()>,
+				)))
+			)?;
+
+		while let Some(notification) = imported.next().await {
+			if notification.origin == BlockOrigin::Own {
+				return Ok(notification.hash)
+			}
+		}
+
+		Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+			ErrorCode::ServerError(2).code(),
+			format!("Import notifications closed"),
+			None::<()>,
+		))))
+	}
+}
@@ -0,0 +1,144 @@
+//! `export-objects` subcommand.
+//!
+//! Streams a range of blocks from the local database, decompresses the mined object
+//! carried in each header and writes it as `<number>_<hash>.obj`, together with a
+//! manifest of the exported blocks.
+
+use std::{fs, io::Write, sync::Arc};
+use codec::{Decode, Encode};
+use log::*;
+use serde::Serialize;
+use sc_client_api::{backend::AuxStore, BlockBackend};
+use sp_blockchain::HeaderBackend;
+use sp_core::{H256, U256};
+use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Header as HeaderT;
+use runtime::opaque::{Block, UncheckedExtrinsic as OpaqueExtrinsic};
+use sc_consensus_poscan::{split_sealed_header, PowAux};
+use sp_consensus_poscan::{try_decompress_obj, POSCAN_COIN_ID};
+
+use crate::cli::{ExportObjectsCommand, OutputFormat};
+
+#[derive(Serialize)]
+struct ManifestEntry {
+	number: u32,
+	hash: H256,
+	author: String,
+	difficulty: U256,
+	/// Milliseconds since the unix epoch, from the timestamp inherent.
+	timestamp: Option<u64>,
+	file: String,
+	obj_hashes: Vec<H256>,
+}
+
+/// Find the timestamp set by the `timestamp.set` inherent of a block.
+pub(crate) fn block_timestamp(body: &[OpaqueExtrinsic]) -> Option<u64> {
+	body.iter().find_map(|xt| {
+		let xt = runtime::UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok()?;
+		match xt.function {
+			runtime::Call::Timestamp(runtime::TimestampCall::set { now }) => Some(now),
+			_ => None,
+		}
+	})
+}
+
+impl ExportObjectsCommand {
+	/// Run the command against the given client.
+	pub async fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: HeaderBackend<Block> + BlockBackend<Block> + AuxStore + Send + Sync,
+	{
+		fs::create_dir_all(&self.output_dir)?;
+
+		let to = self.to.unwrap_or(client.info().best_number);
+		let mut manifest = Vec::new();
+
+		for number in self.from..=to {
+			let block_id = BlockId::Number(number);
+			let header = match client.header(block_id)? {
+				Some(header) => header,
+				None => {
+					warn!("Block #{} is not in the database, stopping", number);
+					break
+				},
+			};
+			let hash = header.hash();
+
+			let sealed = match split_sealed_header::<Block>(&header) {
+				Ok(sealed) => sealed,
+				Err(e) => {
+					warn!("Block #{} skipped: {}", number, e);
+					continue
+				},
+			};
+
+			let mut obj = sealed.poscan_data.obj;
+			if obj.starts_with(b"lzss") {
+				obj = match try_decompress_obj(&obj[4..]) {
+					Some(obj) => obj,
+					None => {
+						warn!("Block #{} skipped: object decompression failed", number);
+						continue
+					},
+				};
+			}
+
+			let file = format!("{}_{:?}.obj", number, hash);
+			fs::write(self.output_dir.join(&file), &obj)?;
+
+			let author = sc_consensus_poscan::app::Public::decode(&mut &sealed.pre_digest[..])
+				.map(|a| a.to_ss58check_with_version(Ss58AddressFormat::from(POSCAN_COIN_ID)))
+				.unwrap_or_default();
+			let difficulty = PowAux::<U256>::read::<_, Block>(client.as_ref(), &hash)
+				.map(|aux| aux.difficulty)
+				.unwrap_or_default();
+			let timestamp = client.block_body(&block_id)?
+				.and_then(|body| block_timestamp(&body));
+
+			manifest.push(ManifestEntry {
+				number,
+				hash,
+				author,
+				difficulty,
+				timestamp,
+				file,
+				obj_hashes: sealed.poscan_data.hashes,
+			});
+
+			if number % 1000 == 0 {
+				info!("Exported up to block #{}", number);
+			}
+		}
+
+		match self.manifest {
+			OutputFormat::Json => {
+				let json = serde_json::to_string_pretty(&manifest)
+					.map_err(|e| format!("Serializing manifest failed: {:?}", e))?;
+				fs::write(self.output_dir.join("manifest.json"), json)?;
+			},
+			OutputFormat::Csv => {
+				let mut f = fs::File::create(self.output_dir.join("manifest.csv"))?;
+				writeln!(f, "number,hash,author,difficulty,timestamp,file,obj_hashes")?;
+				for e in &manifest {
+					let obj_hashes: Vec<String> = e.obj_hashes.iter().map(|h| format!("{:?}", h)).collect();
+					writeln!(
+						f,
+						"{},{:?},{},{},{},{},{}",
+						e.number,
+						e.hash,
+						e.author,
+						e.difficulty,
+						e.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+						e.file,
+						obj_hashes.join(";"),
+					)?;
+				}
+			},
+		}
+
+		info!("Exported {} objects to {:?}", manifest.len(), self.output_dir);
+
+		Ok(())
+	}
+}
@@ -10,8 +10,16 @@ pub(crate) mod service;
 extern crate lazy_static;
 mod cli;
 mod command;
+mod benchmarking;
 mod rpc;
 mod mining_rpc;
+mod network_stats;
+mod rewards_rpc;
+mod dev_seal;
+mod verify_chain;
+mod export_objects;
+mod simulate_difficulty;
+mod simulate_emission;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,198 @@
+//! Network statistics derived from the chain: estimated hashrate, average block time and
+//! difficulty trend over windows of recent blocks.
+//!
+//! The work done in a window is the increase of the total difficulty the PoScan block import
+//! keeps in the aux store, and its duration comes from the timestamp inherents in the block
+//! bodies. Neither needs the state of the first block of a window, which is usually pruned.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use sc_client_api::{AuxStore, BlockBackend};
+use sc_consensus_poscan::PowAux;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_poscan::{
+	Difficulty, DifficultyApi, DifficultyWindowApi, TimestampApi, HOUR_HEIGHT,
+};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, SaturatedConversion}};
+use runtime::opaque::UncheckedExtrinsic as OpaqueExtrinsic;
+
+use crate::export_objects::block_timestamp;
+
+/// Windows, in blocks, the stats are taken over when none are requested. They are read from
+/// block bodies and aux data, so on a node pruning blocks they need that many blocks kept.
+const DEFAULT_WINDOWS: [u64; 3] = [10, HOUR_HEIGHT, 4 * HOUR_HEIGHT];
+/// Maximum number of windows a single call may request.
+const MAX_WINDOWS: usize = 16;
+
+/// Stats over a window of blocks ending at the requested block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowStats {
+	/// Number of blocks in the window, less than requested close to genesis.
+	pub blocks: u64,
+	/// Average block time in milliseconds.
+	pub average_block_time: u64,
+	/// Estimated network hashrate in hashes per second.
+	pub hashrate: Difficulty,
+	/// Difficulty of the first block of the window.
+	pub start_difficulty: Difficulty,
+	/// Change of the difficulty over the window, in percent.
+	pub difficulty_change: f64,
+}
+
+/// Network stats at a block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStats<BlockHash> {
+	/// Block the stats are taken at.
+	pub at: BlockHash,
+	/// Number of that block.
+	pub number: u64,
+	/// Difficulty of the next block.
+	pub difficulty: Difficulty,
+	/// Target block time in milliseconds.
+	pub target_block_time: u64,
+	/// Stats per requested window. Windows are taken on the best chain, and windows with no
+	/// block after genesis or starting at a block whose body has been pruned are left out.
+	pub windows: Vec<WindowStats>,
+}
+
+#[rpc(client, server)]
+pub trait NetworkStatsRpcApi<BlockHash> {
+	/// Network stats at block `at`, the best block by default, over windows of the given
+	/// numbers of blocks.
+	#[method(name = "poscan_networkStats")]
+	fn network_stats(
+		&self,
+		windows: Option<Vec<u64>>,
+		at: Option<BlockHash>,
+	) -> RpcResult<NetworkStats<BlockHash>>;
+}
+
+pub struct NetworkStatsRpc<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> NetworkStatsRpc<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+fn error(code: ErrorCode, message: String) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		code.code(),
+		message,
+		None::<()>,
+	)))
+}
+
+fn to_f64(value: Difficulty) -> f64 {
+	u128::try_from(value).unwrap_or(u128::MAX) as f64
+}
+
+impl<C, Block> NetworkStatsRpc<C, Block>
+	where
+		Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+		C: HeaderBackend<Block> + BlockBackend<Block> + AuxStore + Send + Sync + 'static,
+{
+	/// Timestamp, difficulty and total difficulty of a block, `None` if its body has been
+	/// pruned.
+	fn point(&self, hash: Block::Hash) -> RpcResult<Option<(u64, Difficulty, Difficulty)>> {
+		let body = self.client.block_body(&BlockId::Hash(hash))
+			.map_err(|e| error(ErrorCode::ServerError(1), format!("Can't get block body: {}", e)))?;
+		let timestamp = match body.and_then(|body| block_timestamp(&body)) {
+			Some(timestamp) => timestamp,
+			None => return Ok(None),
+		};
+		let aux = PowAux::<Difficulty>::read::<_, Block>(self.client.as_ref(), &hash)
+			.map_err(|e| error(ErrorCode::ServerError(2), format!("Can't read total difficulty: {}", e)))?;
+
+		Ok(Some((timestamp, aux.difficulty, aux.total_difficulty)))
+	}
+}
+
+impl<C, Block> NetworkStatsRpcApiServer<<Block as BlockT>::Hash> for NetworkStatsRpc<C, Block>
+	where
+		Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block> + AuxStore
+			+ Send + Sync + 'static,
+		C::Api: DifficultyApi<Block, Difficulty>,
+		C::Api: DifficultyWindowApi<Block, u64>,
+		C::Api: TimestampApi<Block, u64>,
+{
+	fn network_stats(
+		&self,
+		windows: Option<Vec<u64>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NetworkStats<<Block as BlockT>::Hash>> {
+		let windows = windows.unwrap_or_else(|| DEFAULT_WINDOWS.to_vec());
+		if windows.len() > MAX_WINDOWS {
+			return Err(error(
+				ErrorCode::InvalidParams,
+				format!("At most {} windows can be requested", MAX_WINDOWS),
+			))
+		}
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let number: u64 = self.client.number(at)
+			.ok()
+			.flatten()
+			.ok_or_else(|| error(ErrorCode::InvalidParams, format!("Unknown block: {}", at)))?
+			.saturated_into();
+
+		let api = self.client.runtime_api();
+		let target_block_time = api.target_block_time(&BlockId::Hash(at))
+			.map_err(|e| error(ErrorCode::ServerError(1), format!("Can't get target block time: {}", e)))?;
+		let difficulty = api.difficulty(&BlockId::Hash(at))
+			.map_err(|e| error(ErrorCode::ServerError(1), format!("Can't get difficulty: {}", e)))?;
+		let end_timestamp = api.timestamp(&BlockId::Hash(at))
+			.map_err(|e| error(ErrorCode::ServerError(1), format!("Can't get timestamp: {}", e)))?;
+		let end_total = PowAux::<Difficulty>::read::<_, Block>(self.client.as_ref(), &at)
+			.map_err(|e| error(ErrorCode::ServerError(2), format!("Can't read total difficulty: {}", e)))?
+			.total_difficulty;
+
+		let mut stats = Vec::with_capacity(windows.len());
+		for blocks in windows {
+			// the genesis block has no timestamp
+			let blocks = blocks.min(number.saturating_sub(1));
+			if blocks == 0 {
+				continue
+			}
+
+			let start = self.client.hash((number - blocks).saturated_into())
+				.ok()
+				.flatten()
+				.ok_or_else(|| error(ErrorCode::ServerError(3), format!("No block at {}", number - blocks)))?;
+			let (start_timestamp, start_difficulty, start_total) = match self.point(start)? {
+				Some(point) => point,
+				None => continue,
+			};
+
+			let duration = end_timestamp.saturating_sub(start_timestamp).max(1);
+			let work = end_total.saturating_sub(start_total);
+			let start = to_f64(start_difficulty).max(1.0);
+
+			stats.push(WindowStats {
+				blocks,
+				average_block_time: duration / blocks,
+				hashrate: work.saturating_mul(Difficulty::from(1000)) / Difficulty::from(duration),
+				start_difficulty,
+				difficulty_change: (to_f64(difficulty) - start) / start * 100.0,
+			});
+		}
+
+		Ok(NetworkStats { at, number, difficulty, target_block_time, windows: stats })
+	}
+}
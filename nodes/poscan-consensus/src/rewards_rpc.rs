@@ -0,0 +1,110 @@
+//! Reward lock schedules of accounts, for wallets to show when rewards unlock.
+
+use std::sync::Arc;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use pallet_rewards::{LockParameters, LockScheduleApi};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// A reward lock expiring at a block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Unlock<BlockNumber, Balance> {
+	/// Block the lock expires at.
+	pub block: BlockNumber,
+	/// Locked amount.
+	pub amount: Balance,
+}
+
+/// Reward locks of an account at a block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockSchedule<BlockHash, BlockNumber, Balance> {
+	/// Block the schedule is taken at.
+	pub at: BlockHash,
+	/// Locks still in force, by expiry block.
+	pub unlocks: Vec<Unlock<BlockNumber, Balance>>,
+	/// Total of the locks still in force.
+	pub total_locked: Balance,
+	/// Expired locks, freed by the `rewards.unlock` call.
+	pub unlockable: Balance,
+	/// Period, in days, new rewards are locked for. Absent for the runtime defaults.
+	pub lock_period: Option<u16>,
+	/// Number of parts new rewards are unlocked in. Absent for the runtime defaults.
+	pub lock_divide: Option<u16>,
+}
+
+#[rpc(client, server)]
+pub trait RewardsRpcApi<BlockHash, AccountId, BlockNumber, Balance> {
+	/// Reward lock schedule of `account` at block `at`, the best block by default.
+	#[method(name = "rewards_lockSchedule")]
+	fn lock_schedule(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<LockSchedule<BlockHash, BlockNumber, Balance>>;
+}
+
+pub struct RewardsRpc<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> RewardsRpc<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance>
+	RewardsRpcApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance> for RewardsRpc<C, Block>
+	where
+		Block: BlockT,
+		AccountId: codec::Codec + DeserializeOwned + Send + Sync + 'static,
+		BlockNumber: codec::Codec + Serialize + Send + Sync + 'static,
+		Balance: codec::Codec + Serialize + Send + Sync + 'static,
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+		C::Api: LockScheduleApi<Block, AccountId, BlockNumber, Balance>,
+{
+	fn lock_schedule(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<LockSchedule<<Block as BlockT>::Hash, BlockNumber, Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let schedule = self.client.runtime_api()
+			.lock_schedule(&BlockId::Hash(at), account)
+			.map_err(|e| JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				ErrorCode::ServerError(1).code(),
+				format!("Can't get lock schedule: {}", e),
+				None::<()>,
+			))))?;
+
+		let (lock_period, lock_divide) = match schedule.lock_params {
+			Some(LockParameters { period, divide }) => (Some(period), Some(divide)),
+			None => (None, None),
+		};
+
+		Ok(LockSchedule {
+			at,
+			unlocks: schedule.unlocks
+				.into_iter()
+				.map(|(block, amount)| Unlock { block, amount })
+				.collect(),
+			total_locked: schedule.locked,
+			unlockable: schedule.unlockable,
+			lock_period,
+			lock_divide,
+		})
+	}
+}
@@ -7,9 +7,10 @@
 
 use std::sync::Arc;
 
-use runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index, Moment};
 use jsonrpsee::RpcModule;
-use sc_client_api::BlockBackend;
+use futures::channel::mpsc::UnboundedSender;
+use sc_client_api::{AuxStore, BlockBackend, BlockchainEvents};
 use sc_rpc::dev::{Dev, DevApiServer};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -26,8 +27,8 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
-	// /// A command stream to send authoring commands to manual seal consensus engine
-	// pub command_sink:Sender<EngineComman>,
+	/// A command stream to request blocks from the dev sealing engine
+	pub command_sink: Option<UnboundedSender<()>>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -37,25 +38,42 @@ pub fn create_full<C, P>(
 where
 	C: ProvideRuntimeApi<Block>,
 	C: BlockBackend<Block>,
+	C: BlockchainEvents<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: AuxStore,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: sp_consensus_poscan::DifficultyApi<Block, sp_consensus_poscan::Difficulty>,
+	C::Api: sp_consensus_poscan::DifficultyWindowApi<Block, Moment>,
+	C::Api: sp_consensus_poscan::TimestampApi<Block, Moment>,
+	C::Api: pallet_rewards::LockScheduleApi<Block, AccountId, BlockNumber, Balance>,
 	P: TransactionPool + 'static,
 {
 	use pallet_contracts_rpc::{Contracts, ContractsApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	use crate::mining_rpc::{MiningRpc, PoscanMiningRpcApiServer};
+	use crate::dev_seal::{DevSealingRpc, DevSealingRpcApiServer};
+	use crate::network_stats::{NetworkStatsRpc, NetworkStatsRpcApiServer};
+	use crate::rewards_rpc::{RewardsRpc, RewardsRpcApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	module.merge(MiningRpc::new(client.clone()).into_rpc())?;
+	module.merge(NetworkStatsRpc::new(client.clone()).into_rpc())?;
+	module.merge(RewardsRpcApiServer::<_, AccountId, BlockNumber, Balance>::into_rpc(
+		RewardsRpc::new(client.clone()),
+	))?;
+
+	if let Some(command_sink) = command_sink {
+		module.merge(DevSealingRpc::new(client.clone(), command_sink).into_rpc())?;
+	}
 
 	// Add a silly RPC that returns constant values
 	// io.extend_with(crate::mining_rpc::PoscanMiningRpc::to_delegate(
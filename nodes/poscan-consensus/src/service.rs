@@ -1,7 +1,7 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 #![allow(clippy::needless_borrow)]
 use runtime::{self, opaque::Block, RuntimeApi};
-use sc_client_api::{ExecutorProvider, BlockBackend};
+use sc_client_api::{ExecutorProvider, BlockBackend, StorageProvider};
 use sc_executor::NativeElseWasmExecutor;
 use sc_consensus::DefaultImportQueue;
 use sc_finality_grandpa::{GrandpaBlockImport, grandpa_peers_set_config};
@@ -22,8 +22,15 @@ use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
 use sp_runtime::traits::Block as BlockT;
 use sp_core::crypto::{Ss58Codec,UncheckedFrom, Ss58AddressFormat, set_default_ss58_version};
 use sp_core::Pair;
-use sp_consensus_poscan::{POSCAN_COIN_ID,POSCAN_ALGO_GRID2D} ;
+use sp_consensus_poscan::{POSCAN_COIN_ID,POSCAN_ALGO_GRID2D, BLOCK_TIME} ;
 use async_trait::async_trait;
+use sc_transaction_pool_api::TransactionPool;
+use futures::{channel::mpsc, StreamExt};
+use sp_core::{hashing::{blake2_256, twox_128}, storage::StorageKey};
+use sp_blockchain::HeaderBackend;
+use pallet_poscan::inherents::MinedObjectData;
+use sp_runtime::generic::BlockId;
+use crate::cli::Sealing;
 
 pub struct MiningProposal {
 	pub id: i32,
@@ -90,24 +97,80 @@ pub fn decode_author(
 	}
 }
 
-type FullClient =
+pub type FullClient =
        sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
 
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
-pub struct CreateInherentDataProviders;
+pub struct CreateInherentDataProviders {
+	client: Arc<FullClient>,
+	/// Set in dev sealing mode. Blocks are then timestamped `BLOCK_TIME` after their parent,
+	/// however fast they are sealed, so that the difficulty does not climb.
+	dev_sealing: bool,
+}
+
+impl CreateInherentDataProviders {
+	pub fn new(client: Arc<FullClient>, dev_sealing: bool) -> Self {
+		Self { client, dev_sealing }
+	}
+
+	fn timestamp(&self, parent: <Block as BlockT>::Hash) -> sp_blockchain::Result<sp_timestamp::InherentDataProvider> {
+		if !self.dev_sealing {
+			return Ok(sp_timestamp::InherentDataProvider::from_system_time())
+		}
+
+		let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+		let parent_timestamp = self.client.storage(&BlockId::Hash(parent), &key)?
+			.and_then(|data| u64::decode(&mut &data.0[..]).ok())
+			.unwrap_or_default();
+
+		if parent_timestamp == 0 {
+			Ok(sp_timestamp::InherentDataProvider::from_system_time())
+		} else {
+			Ok(sp_timestamp::InherentDataProvider::new((parent_timestamp + BLOCK_TIME).into()))
+		}
+	}
+
+	/// Object mined in the parent block, read from its header. Genesis has none.
+	fn mined_object(&self, parent: <Block as BlockT>::Hash) -> sp_blockchain::Result<Option<MinedObjectData>> {
+		let header = match self.client.header(BlockId::Hash(parent))? {
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		if header.number == 0 {
+			return Ok(None)
+		}
+
+		let sealed = sc_consensus_poscan::split_sealed_header::<Block>(&header)
+			.map_err(|e| sp_blockchain::Error::Application(Box::from(String::from(e))))?;
+		let obj = &sealed.poscan_data.obj;
+		let obj_hash = match obj.starts_with(b"lzss") {
+			true => sp_consensus_poscan::try_decompress_obj(&obj[4..]).map(|obj| blake2_256(&obj)),
+			false => Some(blake2_256(obj)),
+		};
+
+		Ok(Some(MinedObjectData {
+			alg_id: sealed.poscan_data.alg_id,
+			hashes: sealed.poscan_data.hashes,
+			obj_hash: obj_hash.map(H256::from),
+		}))
+	}
+}
 
 #[async_trait]
 impl sp_inherents::CreateInherentDataProviders<Block, ()> for CreateInherentDataProviders {
-	type InherentDataProviders = sp_timestamp::InherentDataProvider;
+	type InherentDataProviders = (sp_timestamp::InherentDataProvider, pallet_poscan::inherents::InherentDataProvider);
 
 	async fn create_inherent_data_providers(
 		&self,
-		_parent: <Block as BlockT>::Hash,
+		parent: <Block as BlockT>::Hash,
 		_extra_args: (),
 	) -> Result<Self::InherentDataProviders, Box<dyn std::error::Error + Send + Sync>> {
-		Ok(sp_timestamp::InherentDataProvider::from_system_time())
+		let timestamp = self.timestamp(parent)?;
+		let mined_object = pallet_poscan::inherents::InherentDataProvider(self.mined_object(parent)?);
+
+		Ok((timestamp, mined_object))
 	}
 }
 
@@ -118,6 +181,7 @@ impl sp_inherents::CreateInherentDataProviders<Block, ()> for CreateInherentData
 #[allow(clippy::type_complexity)]
 pub fn new_partial(
 	config: &Configuration,
+	dev_sealing: bool,
 	// check_inherents_after: u32,
 	// enable_weak_subjectivity: bool,
 ) -> Result<
@@ -205,7 +269,7 @@ pub fn new_partial(
 		poscan_grid2d::PoscanAlgorithm::new(client.clone()),
 		0, // check inherents starting at block 0
 		select_chain.clone(),
-		CreateInherentDataProviders,
+		CreateInherentDataProviders::new(client.clone(), dev_sealing),
 		can_author_with,
 	);
 
@@ -236,7 +300,12 @@ pub fn new_full(
 	mut config: Configuration,
 	author: Option<&str>,
 	threads: usize,
+	sealing: Option<Sealing>,
 ) -> Result<TaskManager, ServiceError> {
+	if sealing.is_some() && config.chain_spec.chain_type() != sc_service::ChainType::Development {
+		return Err(ServiceError::Other("--sealing is only available on development chains".to_string()))
+	}
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -246,7 +315,7 @@ pub fn new_full(
 		select_chain,
 		transaction_pool,
 		other: (pow_block_import, grandpa_link, shared_voter_state, mut telemetry),
-	} = new_partial(&config)?;
+	} = new_partial(&config, sealing.is_some())?;
 
 	let grandpa_protocol_name = sc_finality_grandpa::protocol_standard_name(
 		&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
@@ -284,13 +353,20 @@ pub fn new_full(
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let enable_grandpa = !config.disable_grandpa;
 
+	let (command_sink, commands) = mpsc::unbounded::<()>();
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let command_sink = sealing.map(|_| command_sink.clone());
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
@@ -312,7 +388,72 @@ pub fn new_full(
 			telemetry: telemetry.as_mut(),
 		})?;
 
-	if is_authority {
+	if let Some(sealing) = sealing {
+		let pair = sc_consensus_poscan::app::Pair::from_string(crate::dev_seal::DEV_SEALING_SURI, None)
+			.map_err(|_| ServiceError::Other("Unable to seal: invalid dev key".to_string()))?;
+		let author = pair.public();
+
+		let trigger = match sealing {
+			Sealing::Manual => commands.boxed(),
+			Sealing::Instant => futures::stream::select(
+				commands,
+				transaction_pool.import_notification_stream().map(|_| ()),
+			).boxed(),
+		};
+
+		let proposer = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool,
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		let can_author_with =
+			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
+
+		let (worker, worker_task) = sc_consensus_poscan::start_mining_worker_with_trigger(
+			Box::new(pow_block_import),
+			client.clone(),
+			select_chain,
+			PoscanAlgorithm::new(client.clone()),
+			proposer,
+			network.clone(),
+			network.clone(),
+			Some(author.encode()),
+			CreateInherentDataProviders::new(client.clone(), true),
+			trigger,
+			// how long to take to actually build the block (i.e. executing extrinsics)
+			Duration::from_secs(10),
+			can_author_with,
+		);
+
+		task_manager
+			.spawn_essential_handle()
+			.spawn_blocking("poscan", None,  worker_task);
+
+		info!(">>> Spawn {:?} sealing with author {}", sealing, author.to_ss58check());
+
+		thread::spawn(move || loop {
+			let metadata = match worker.metadata() {
+				Some(metadata) => metadata,
+				None => {
+					thread::sleep(Duration::from_millis(50));
+					continue
+				},
+			};
+
+			match crate::dev_seal::seal_build(&metadata, &pair) {
+				Some((seal, psdata)) => {
+					let _ = futures::executor::block_on(worker.submit(seal, &psdata));
+				},
+				None => {
+					warn!("Unable to seal block on top of {}: difficulty {} not met", metadata.best_hash, metadata.difficulty);
+					thread::sleep(Duration::from_secs(1));
+				},
+			}
+		});
+	} else if is_authority {
 		let author = decode_author(author, keystore_container.sync_keystore(), keystore_path)?;
 
 		let proposer = sc_basic_authorship::ProposerFactory::new(
@@ -335,7 +476,7 @@ pub fn new_full(
 			network.clone(),
 			network.clone(),
 			Some(author.encode()),
-			CreateInherentDataProviders,
+			CreateInherentDataProviders::new(client.clone(), false),
 			// time to wait for a new block before starting to mine a new one
 			Duration::from_secs(10),
 			// how long to take to actually build the block (i.e. executing extrinsics)
@@ -0,0 +1,212 @@
+//! `simulate-difficulty` subcommand.
+//!
+//! Replays the retarget of the difficulty pallet either over a hashrate scenario, where
+//! each block takes `difficulty / hashrate` seconds, or over recorded block timestamps,
+//! and prints the resulting difficulty and block time series.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU32, Ordering};
+use serde::Serialize;
+use sp_core::U256;
+use sp_consensus_poscan::{Difficulty, MAX_DIFFICULTY, MIN_DIFFICULTY};
+use pallet_difficulty::{Asert, DifficultyAdjustment, DifficultyAndTimestamp, Lwma, MovingAverage};
+
+use crate::cli::{DifficultyAlgorithm, OutputFormat, SimulateDifficultyCommand};
+
+type Window = [Option<DifficultyAndTimestamp<u64>>];
+
+static HALF_LIFE: AtomicU32 = AtomicU32::new(0);
+
+/// Half-life of `Asert` set from the command line.
+struct HalfLife;
+
+impl sp_core::Get<u32> for HalfLife {
+	fn get() -> u32 {
+		HALF_LIFE.load(Ordering::Relaxed)
+	}
+}
+
+#[derive(Serialize)]
+struct SimulatedBlock {
+	number: u64,
+	/// Milliseconds since the start of the series.
+	timestamp: u64,
+	/// Milliseconds since the previous block.
+	block_time: Option<u64>,
+	/// Difficulty the block was mined at.
+	difficulty: U256,
+}
+
+/// Mirrors the window kept in `PastDifficultiesAndTimestamps`.
+struct Simulator {
+	window: Vec<Option<DifficultyAndTimestamp<u64>>>,
+	initial_difficulty: Difficulty,
+	block_time: u128,
+	/// `DifficultyAdjustment::next_difficulty` of the simulated algorithm.
+	adjust: fn(&Window, Difficulty, u128) -> Difficulty,
+	/// Difficulty bounds set by governance.
+	bounds: (Difficulty, Difficulty),
+	difficulty: Difficulty,
+	series: Vec<SimulatedBlock>,
+}
+
+impl Simulator {
+	fn new(
+		window: usize,
+		initial_difficulty: Difficulty,
+		block_time: u128,
+		adjust: fn(&Window, Difficulty, u128) -> Difficulty,
+		bounds: (Difficulty, Difficulty),
+	) -> Self {
+		Simulator {
+			window: vec![None; window],
+			initial_difficulty,
+			block_time,
+			adjust,
+			bounds,
+			difficulty: initial_difficulty,
+			series: Vec::new(),
+		}
+	}
+
+	/// Import a block found at `timestamp` with the current difficulty and retarget,
+	/// as `on_timestamp_set` does.
+	fn push(&mut self, timestamp: u64) {
+		let block_time = self.series.last().map(|b| timestamp.saturating_sub(b.timestamp));
+		self.series.push(SimulatedBlock {
+			number: self.series.len() as u64 + 1,
+			timestamp,
+			block_time,
+			difficulty: self.difficulty,
+		});
+
+		self.window.rotate_left(1);
+		let last = self.window.len() - 1;
+		self.window[last] = Some(DifficultyAndTimestamp { difficulty: self.difficulty, timestamp });
+
+		let (min, max) = self.bounds;
+		self.difficulty = (self.adjust)(&self.window, self.initial_difficulty, self.block_time)
+			.max(min)
+			.min(max);
+	}
+}
+
+/// Read timestamps from the `timestamp` column of a CSV file with a header, or from the
+/// first column of a file without one.
+fn read_timestamps(path: &std::path::Path) -> sc_cli::Result<Vec<u64>> {
+	let content = std::fs::read_to_string(path)?;
+	let mut lines = content.lines().filter(|l| !l.trim().is_empty()).peekable();
+
+	let mut column = 0;
+	if let Some(first) = lines.peek() {
+		let fields: Vec<&str> = first.split(',').map(|f| f.trim()).collect();
+		if let Some(i) = fields.iter().position(|f| *f == "timestamp") {
+			column = i;
+			lines.next();
+		} else if fields[0].parse::<u64>().is_err() {
+			lines.next();
+		}
+	}
+
+	let mut timestamps = Vec::new();
+	for (i, line) in lines.enumerate() {
+		match line.split(',').nth(column).map(|f| f.trim()) {
+			Some("") | None => continue,
+			Some(field) => timestamps.push(field.parse().map_err(|e| {
+				sc_cli::Error::Input(format!("Invalid timestamp {} on row {}: {}", field, i + 1, e))
+			})?),
+		}
+	}
+
+	Ok(timestamps)
+}
+
+impl SimulateDifficultyCommand {
+	/// Run the simulation.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		if self.window < 2 {
+			return Err(sc_cli::Error::Input("Window must be at least 2 blocks".into()))
+		}
+		if self.block_time == 0 {
+			return Err(sc_cli::Error::Input("Block time must be positive".into()))
+		}
+		// as checked by `set_difficulty_bounds`
+		let min = self.min_difficulty.unwrap_or(MIN_DIFFICULTY);
+		let max = self.max_difficulty.unwrap_or(MAX_DIFFICULTY);
+		if min < MIN_DIFFICULTY || min > max {
+			return Err(sc_cli::Error::Input(format!(
+				"Difficulty bounds must be within {} and {}, min first",
+				MIN_DIFFICULTY, MAX_DIFFICULTY,
+			)))
+		}
+
+		let adjust: fn(&Window, Difficulty, u128) -> Difficulty = match self.algorithm {
+			DifficultyAlgorithm::MovingAverage =>
+				<MovingAverage as DifficultyAdjustment<u64>>::next_difficulty,
+			DifficultyAlgorithm::Lwma => <Lwma as DifficultyAdjustment<u64>>::next_difficulty,
+			DifficultyAlgorithm::Asert => {
+				if self.half_life == 0 {
+					return Err(sc_cli::Error::Input("Half-life must be positive".into()))
+				}
+				HALF_LIFE.store(self.half_life, Ordering::Relaxed);
+				<Asert<HalfLife> as DifficultyAdjustment<u64>>::next_difficulty
+			},
+		};
+		let mut sim = Simulator::new(
+			self.window as usize,
+			U256::from(self.initial_difficulty),
+			self.block_time as u128,
+			adjust,
+			(U256::from(min), U256::from(max)),
+		);
+
+		match &self.timestamps {
+			Some(path) => {
+				let timestamps = read_timestamps(path)?;
+				let start = timestamps.first().copied().unwrap_or_default();
+				for timestamp in timestamps {
+					sim.push(timestamp.saturating_sub(start));
+				}
+			},
+			None => {
+				let mut timestamp = 0u64;
+				for step in &self.hashrate {
+					for _ in 0..step.blocks {
+						// difficulty never exceeds MAX_DIFFICULTY, which fits in u128
+						let expected_ms = sim.difficulty.low_u128() as f64 / step.hashrate * 1000.0;
+						timestamp = timestamp.saturating_add(expected_ms as u64);
+						sim.push(timestamp);
+					}
+				}
+			},
+		}
+
+		let mut out: Box<dyn Write> = match &self.output {
+			Some(path) => Box::new(std::fs::File::create(path)?),
+			None => Box::new(std::io::stdout()),
+		};
+
+		match self.format {
+			OutputFormat::Json => {
+				let json = serde_json::to_string_pretty(&sim.series)
+					.map_err(|e| format!("Serializing series failed: {:?}", e))?;
+				writeln!(out, "{}", json)?;
+			},
+			OutputFormat::Csv => {
+				writeln!(out, "number,timestamp,block_time,difficulty")?;
+				for b in &sim.series {
+					writeln!(
+						out,
+						"{},{},{},{}",
+						b.number,
+						b.timestamp,
+						b.block_time.map(|t| t.to_string()).unwrap_or_default(),
+						b.difficulty,
+					)?;
+				}
+			},
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,171 @@
+//! `verify-chain` subcommand.
+//!
+//! Walks a range of blocks from the local database and runs the PoScan seal checks of
+//! `PoscanAlgorithm::verify` against every one of them, using the difficulty recorded
+//! in `PowAux` at import time. The result is printed as a JSON report.
+
+use std::{collections::HashMap, sync::Arc};
+use log::*;
+use serde::Serialize;
+use sc_client_api::backend::AuxStore;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{H256, U256};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Header as HeaderT};
+use runtime::opaque::Block;
+use sc_consensus_poscan::{split_sealed_header, PowAlgorithm, PowAux};
+use sp_consensus_poscan::{DifficultyApi, MAX_MINING_OBJ_LEN, POSCAN_ALGO_GRID2D};
+use poscan_grid2d::{check_seal, PoscanAlgorithm};
+
+use crate::cli::VerifyChainCommand;
+
+#[derive(Serialize)]
+struct BlockFailure {
+	number: u32,
+	hash: H256,
+	reason: String,
+	/// Difficulty stored in `PowAux` when the block was imported.
+	stored_difficulty: Option<U256>,
+	/// Difficulty the runtime returns at the parent block, if its state is available.
+	runtime_difficulty: Option<U256>,
+}
+
+#[derive(Serialize)]
+struct DuplicateHash {
+	obj_hash: H256,
+	first_block: u32,
+	block: u32,
+}
+
+#[derive(Serialize)]
+struct VerifyChainReport {
+	from: u32,
+	to: u32,
+	checked: u32,
+	failures: Vec<BlockFailure>,
+	duplicates: Vec<DuplicateHash>,
+}
+
+impl VerifyChainCommand {
+	/// Run the command against the given client.
+	pub async fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore + Send + Sync,
+		C::Api: DifficultyApi<Block, U256>,
+	{
+		let algorithm = PoscanAlgorithm::new(client.clone());
+		let to = self.to.unwrap_or(client.info().best_number);
+
+		let mut last = to;
+		let mut checked = 0;
+		let mut failures = Vec::new();
+		let mut duplicates = Vec::new();
+		let mut seen: HashMap<H256, u32> = HashMap::new();
+
+		for number in self.from..=to {
+			let header = match client.header(BlockId::Number(number))? {
+				Some(header) => header,
+				None => {
+					warn!("Block #{} is not in the database, stopping", number);
+					last = number.saturating_sub(1);
+					break
+				},
+			};
+			let hash = header.hash();
+			let parent = *header.parent_hash();
+
+			let stored_difficulty = PowAux::<U256>::read::<_, Block>(client.as_ref(), &hash)
+				.ok()
+				.map(|aux| aux.difficulty)
+				.filter(|d| !d.is_zero());
+			let runtime_difficulty = PowAlgorithm::<Block>::difficulty(&algorithm, parent).ok();
+
+			let mut fail = |reason: String| failures.push(BlockFailure {
+				number,
+				hash,
+				reason,
+				stored_difficulty,
+				runtime_difficulty,
+			});
+
+			checked += 1;
+
+			let sealed = match split_sealed_header::<Block>(&header) {
+				Ok(sealed) => sealed,
+				Err(e) => {
+					fail(format!("{}", e));
+					continue
+				},
+			};
+
+			if sealed.poscan_data.alg_id != POSCAN_ALGO_GRID2D {
+				fail("UnknownAlgorithm".to_string());
+				continue
+			}
+			if sealed.poscan_data.obj.len() > MAX_MINING_OBJ_LEN {
+				fail("ObjectTooLarge".to_string());
+				continue
+			}
+
+			let difficulty = match stored_difficulty {
+				Some(difficulty) => difficulty,
+				None => {
+					fail("NoStoredDifficulty".to_string());
+					continue
+				},
+			};
+
+			if let Some(runtime_difficulty) = runtime_difficulty {
+				if runtime_difficulty != difficulty {
+					fail("DifficultyMismatch".to_string());
+				}
+			}
+
+			if let Err(failure) = check_seal(
+				&parent,
+				&sealed.pre_header.hash(),
+				Some(sealed.pre_digest.as_slice()),
+				&sealed.seal,
+				difficulty,
+				&sealed.poscan_data,
+			) {
+				fail(format!("{:?}", failure));
+			}
+
+			if let Some(obj_hash) = sealed.poscan_data.hashes.first() {
+				if let Some(first_block) = seen.get(obj_hash) {
+					duplicates.push(DuplicateHash {
+						obj_hash: *obj_hash,
+						first_block: *first_block,
+						block: number,
+					});
+				} else {
+					seen.insert(*obj_hash, number);
+				}
+			}
+
+			if number % 1000 == 0 {
+				info!("Verified up to block #{}", number);
+			}
+		}
+
+		let report = VerifyChainReport { from: self.from, to: last, checked, failures, duplicates };
+		let json = serde_json::to_string_pretty(&report)
+			.map_err(|e| format!("Serializing report failed: {:?}", e))?;
+		match &self.output {
+			Some(path) => std::fs::write(path, json)?,
+			None => println!("{}", json),
+		}
+
+		if report.failures.is_empty() && report.duplicates.is_empty() {
+			Ok(())
+		} else {
+			Err(sc_cli::Error::Input(format!(
+				"{} invalid block(s), {} duplicated object hash(es)",
+				report.failures.len(),
+				report.duplicates.len(),
+			)))
+		}
+	}
+}
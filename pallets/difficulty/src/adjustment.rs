@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of 3DPass.
+//
+// Copyright (c) 2022 3DPass.
+//
+// 3DPass is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// 3DPass is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
+
+//! Difficulty adjustment algorithms.
+
+use crate::{next_difficulty, DifficultyAndTimestamp, RetargetParams};
+use frame_support::traits::Get;
+use sp_consensus_poscan::{Difficulty, MAX_DIFFICULTY, MIN_DIFFICULTY};
+use sp_core::U256;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Algorithm computing the next difficulty from a window of past blocks.
+pub trait DifficultyAdjustment<M> {
+	/// Compute the next difficulty from a window of past difficulties and timestamps,
+	/// from earliest to latest. Empty slots are treated as blocks of `initial_difficulty`
+	/// found exactly on target.
+	fn next_difficulty(
+		data: &[Option<DifficultyAndTimestamp<M>>],
+		initial_difficulty: Difficulty,
+		block_time: u128,
+	) -> Difficulty;
+}
+
+/// Damped and clamped moving average over the window, see [`next_difficulty`].
+pub struct MovingAverage;
+
+impl<M: Copy + UniqueSaturatedInto<u128>> DifficultyAdjustment<M> for MovingAverage {
+	fn next_difficulty(
+		data: &[Option<DifficultyAndTimestamp<M>>],
+		initial_difficulty: Difficulty,
+		block_time: u128,
+	) -> Difficulty {
+		let params = RetargetParams::default_with_block_time(block_time);
+		next_difficulty(data, initial_difficulty, &params)
+	}
+}
+
+/// Linearly weighted moving average (LWMA-1): the latest solve times weigh the most, so the
+/// difficulty follows hashrate changes within a fraction of the window.
+pub struct Lwma;
+
+impl<M: Copy + UniqueSaturatedInto<u128>> DifficultyAdjustment<M> for Lwma {
+	fn next_difficulty(
+		data: &[Option<DifficultyAndTimestamp<M>>],
+		initial_difficulty: Difficulty,
+		block_time: u128,
+	) -> Difficulty {
+		let solve_times = solve_times(data, block_time);
+		let n = solve_times.len() as u128;
+		if n == 0 {
+			return clamp_difficulty(last_difficulty(data, initial_difficulty))
+		}
+
+		// solve times are capped so that a single forged timestamp can't crash the difficulty
+		let weighted = solve_times
+			.iter()
+			.enumerate()
+			.map(|(i, t)| (i as u128 + 1).saturating_mul((*t).max(1).min(6 * block_time)))
+			.fold(0u128, |sum, t| sum.saturating_add(t));
+		let k = n * (n + 1) / 2;
+		// ... and so that a burst of fast blocks raises it at most tenfold
+		let weighted = weighted.max(k.saturating_mul(block_time) / 10).max(1);
+
+		let diff_sum = data[1..]
+			.iter()
+			.map(|d| d.map_or(initial_difficulty, |d| d.difficulty))
+			.fold(U256::zero(), |sum, d| sum.saturating_add(d));
+
+		clamp_difficulty(
+			diff_sum.saturating_mul(U256::from(k).saturating_mul(U256::from(block_time)))
+				/ (U256::from(n) * U256::from(weighted)),
+		)
+	}
+}
+
+/// ASERT-style exponential adjustment: the difficulty halves (doubles) for every
+/// `HalfLife` blocks the window is behind (ahead of) schedule, relative to its earliest
+/// block.
+pub struct Asert<HalfLife>(PhantomData<HalfLife>);
+
+impl<M, HalfLife> DifficultyAdjustment<M> for Asert<HalfLife>
+where
+	M: Copy + UniqueSaturatedInto<u128>,
+	HalfLife: Get<u32>,
+{
+	fn next_difficulty(
+		data: &[Option<DifficultyAndTimestamp<M>>],
+		initial_difficulty: Difficulty,
+		block_time: u128,
+	) -> Difficulty {
+		let solve_times = solve_times(data, block_time);
+		let anchor = match data.first() {
+			Some(d) => d.map_or(initial_difficulty, |d| d.difficulty),
+			None => return clamp_difficulty(initial_difficulty),
+		};
+
+		let elapsed = solve_times.iter().fold(0u128, |sum, t| sum.saturating_add(*t));
+		let scheduled = block_time.saturating_mul(solve_times.len() as u128);
+		let half_life = (HalfLife::get() as u128).saturating_mul(block_time).max(1);
+
+		// 16.16 fixed point number of half-lives the window is behind schedule
+		let behind =
+			elapsed.min(i128::MAX as u128) as i128 - scheduled.min(i128::MAX as u128) as i128;
+		let exponent = behind.saturating_mul(1 << 16) / half_life as i128;
+
+		clamp_difficulty(exp2_neg(anchor, exponent))
+	}
+}
+
+/// `value * 2^(-exponent / 2^16)`, saturating at `U256::max_value()`.
+///
+/// The fractional power of two uses the cubic approximation of aserti3-2d, which is
+/// within 0.013% of the exact value.
+fn exp2_neg(value: U256, exponent: i128) -> U256 {
+	let shifts = exponent >> 16;
+	let frac = (exponent & 0xffff) as u128;
+	let factor = 65536u128 +
+		((195_766_423_245_049u128 * frac +
+			971_821_376u128 * frac * frac +
+			5_127u128 * frac * frac * frac +
+			(1u128 << 47)) >> 48);
+
+	let value = value.saturating_mul(U256::from(65536u128)) / U256::from(factor);
+	if shifts >= 0 {
+		if shifts >= 256 {
+			U256::zero()
+		} else {
+			value >> shifts as usize
+		}
+	} else {
+		let shifts = shifts.unsigned_abs();
+		if value.is_zero() {
+			value
+		} else if shifts >= value.leading_zeros() as u128 {
+			U256::max_value()
+		} else {
+			value << shifts as usize
+		}
+	}
+}
+
+/// Solve times of the blocks of the window but the earliest. A slot next to an empty one is
+/// taken as on target.
+fn solve_times<M: Copy + UniqueSaturatedInto<u128>>(
+	data: &[Option<DifficultyAndTimestamp<M>>],
+	block_time: u128,
+) -> Vec<u128> {
+	data.windows(2)
+		.map(|w| {
+			let prev: Option<u128> = w[0].map(|d| d.timestamp.unique_saturated_into());
+			let cur: Option<u128> = w[1].map(|d| d.timestamp.unique_saturated_into());
+			match (prev, cur) {
+				(Some(prev), Some(cur)) => cur.saturating_sub(prev),
+				_ => block_time,
+			}
+		})
+		.collect()
+}
+
+fn last_difficulty<M: Copy>(
+	data: &[Option<DifficultyAndTimestamp<M>>],
+	initial_difficulty: Difficulty,
+) -> Difficulty {
+	data.last().and_then(|d| d.map(|d| d.difficulty)).unwrap_or(initial_difficulty)
+}
+
+fn clamp_difficulty(difficulty: Difficulty) -> Difficulty {
+	difficulty.max(U256::from(MIN_DIFFICULTY)).min(U256::from(MAX_DIFFICULTY))
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of 3DPass.
+//
+// Copyright (c) 2022 3DPass.
+//
+// 3DPass is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// 3DPass is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarking for Difficulty pallet.
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+
+benchmarks! {
+	// Worst case: the current difficulty is clamped to the new bounds.
+	set_difficulty_bounds {
+		let origin = T::UpdateOrigin::successful_origin();
+		CurrentDifficulty::<T>::put(U256::from(MAX_DIFFICULTY));
+		let max = U256::from(MIN_DIFFICULTY * 2);
+	}: _<T::Origin>(origin, None, Some(max))
+	verify {
+		assert_eq!(Pallet::<T>::difficulty(), max);
+	}
+
+	// Worst case: the whole retarget window is filled.
+	reset_difficulty {
+		let origin = T::UpdateOrigin::successful_origin();
+		let window = (0..T::DifficultyWindow::get())
+			.map(|i| Some(DifficultyAndTimestamp {
+				difficulty: U256::from(MIN_DIFFICULTY),
+				timestamp: T::Moment::from(i),
+			}))
+			.collect::<Vec<_>>();
+		PastDifficultiesAndTimestamps::<T>::put(window);
+		let difficulty = U256::from(MIN_DIFFICULTY * 2);
+	}: _<T::Origin>(origin, difficulty)
+	verify {
+		assert_eq!(Pallet::<T>::difficulty(), difficulty);
+	}
+
+	set_target_block_time {
+		let origin = T::UpdateOrigin::successful_origin();
+		let block_time = T::Moment::from(30_000u32);
+	}: _<T::Origin>(origin, Some(block_time))
+	verify {
+		assert_eq!(Pallet::<T>::target_block_time(), block_time);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -21,22 +21,38 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod adjustment;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
+mod migrations;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 use codec::{Decode, Encode};
 use frame_support::{
-	decl_module, decl_storage,
-	traits::{Get, OnTimestampSet},
+	traits::{Get, OnTimestampSet, StorageVersion},
+	weights::Weight,
 };
 use sp_consensus_poscan::{
-	Difficulty, CLAMP_FACTOR, DIFFICULTY_ADJUST_WINDOW, DIFFICULTY_DAMP_FACTOR, MAX_DIFFICULTY,
-	MIN_DIFFICULTY,
+	Difficulty, CLAMP_FACTOR, DIFFICULTY_DAMP_FACTOR, MAX_DIFFICULTY, MIN_DIFFICULTY,
 };
 use scale_info::TypeInfo;
 use sp_core::U256;
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_runtime::traits::{UniqueSaturatedInto, Zero};
 use sp_std::cmp::{max, min};
+use sp_std::prelude::*;
 #[cfg(not(feature = "std"))]
 use sp_std::vec;
 
+pub use adjustment::{Asert, DifficultyAdjustment, Lwma, MovingAverage};
+pub use pallet::*;
+
+/// The current storage version.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 #[derive(Encode, Decode, TypeInfo, Clone, Copy, Eq, PartialEq, Debug)]
 pub struct DifficultyAndTimestamp<M> {
 	pub difficulty: Difficulty,
@@ -53,96 +69,342 @@ pub fn clamp(actual: u128, goal: u128, clamp_factor: u128) -> u128 {
 	max(goal / clamp_factor, min(actual, goal * clamp_factor))
 }
 
-pub trait Config: pallet_timestamp::Config {
-	/// Target block time in millseconds.
-	type TargetBlockTime: Get<Self::Moment>;
+/// Parameters of the difficulty retarget.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct RetargetParams {
+	/// Target block time in milliseconds.
+	pub block_time: u128,
+	/// Dampening factor applied to the observed window duration.
+	pub damp_factor: u128,
+	/// The adjusted window duration is kept within this factor of the goal.
+	pub clamp_factor: u128,
 }
 
-decl_storage! {
-	trait Store for Module<T: Config> as Difficulty {
-		/// Past difficulties and timestamps, from earliest to latest.
-		PastDifficultiesAndTimestamps:
-		[Option<DifficultyAndTimestamp<T::Moment>>; 60]
-			= [None; DIFFICULTY_ADJUST_WINDOW as usize];
-		/// Current difficulty.
-		pub CurrentDifficulty get(fn difficulty) build(|config: &GenesisConfig| {
-			config.initial_difficulty
-		}): Difficulty;
-		/// Initial difficulty.
-		pub InitialDifficulty config(initial_difficulty): Difficulty;
+impl RetargetParams {
+	/// Parameters used by the runtime for the given target block time.
+	pub fn default_with_block_time(block_time: u128) -> Self {
+		Self { block_time, damp_factor: DIFFICULTY_DAMP_FACTOR, clamp_factor: CLAMP_FACTOR }
 	}
 }
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		/// Target block time in milliseconds.
-		const TargetBlockTime: T::Moment = T::TargetBlockTime::get();
+/// Compute the next difficulty from a window of past difficulties and timestamps,
+/// from earliest to latest. Empty slots are treated as blocks of `initial_difficulty`
+/// found exactly on target.
+pub fn next_difficulty<M>(
+	data: &[Option<DifficultyAndTimestamp<M>>],
+	initial_difficulty: Difficulty,
+	params: &RetargetParams,
+) -> Difficulty
+where
+	M: Copy + UniqueSaturatedInto<u128>,
+{
+	let block_time = params.block_time;
+	let block_time_window = data.len() as u128 * block_time;
+
+	let mut ts_delta = 0;
+	for i in 1..data.len() {
+		let prev: Option<u128> = data[i - 1].map(|d| d.timestamp.unique_saturated_into());
+		let cur: Option<u128> = data[i].map(|d| d.timestamp.unique_saturated_into());
+
+		let delta = match (prev, cur) {
+			(Some(prev), Some(cur)) => cur.saturating_sub(prev),
+			_ => block_time,
+		};
+		ts_delta += delta;
+	}
+
+	if ts_delta == 0 {
+		ts_delta = 1;
+	}
+
+	let mut diff_sum = U256::zero();
+	for d in data {
+		let diff = match d.map(|d| d.difficulty) {
+			Some(diff) => diff,
+			None => initial_difficulty,
+		};
+		diff_sum += diff;
 	}
+
+	if diff_sum < U256::from(MIN_DIFFICULTY) {
+		diff_sum = U256::from(MIN_DIFFICULTY);
+	}
+
+	// adjust time delta toward goal subject to dampening and clamping
+	let adj_ts = clamp(
+		damp(ts_delta, block_time_window, params.damp_factor),
+		block_time_window,
+		params.clamp_factor,
+	);
+
+	// minimum difficulty avoids getting stuck due to dampening
+	min(
+		U256::from(MAX_DIFFICULTY),
+		max(
+			U256::from(MIN_DIFFICULTY),
+			diff_sum * U256::from(block_time) / U256::from(adj_ts),
+		),
+	)
 }
 
-impl<T: Config> OnTimestampSet<T::Moment> for Module<T> {
-	fn on_timestamp_set(now: T::Moment) {
-		let block_time =
-			UniqueSaturatedInto::<u128>::unique_saturated_into(T::TargetBlockTime::get());
-		let block_time_window = DIFFICULTY_ADJUST_WINDOW as u128 * block_time;
+pub trait WeightInfo {
+	fn set_difficulty_bounds() -> Weight;
+	fn reset_difficulty() -> Weight;
+	fn set_target_block_time() -> Weight;
+}
 
-		let mut data = PastDifficultiesAndTimestamps::<T>::get();
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_timestamp::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Target block time in millseconds.
+		#[pallet::constant]
+		type TargetBlockTime: Get<Self::Moment>;
+		/// Number of past blocks the difficulty is retargeted over.
+		#[pallet::constant]
+		type DifficultyWindow: Get<u32>;
+		/// Algorithm computing the next difficulty from the window.
+		type DifficultyAdjustment: DifficultyAdjustment<Self::Moment>;
+		/// Origin allowed to override the difficulty, its bounds and the target block time.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// Weights for this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Past difficulties and timestamps, from earliest to latest.
+	#[pallet::storage]
+	pub(super) type PastDifficultiesAndTimestamps<T: Config> =
+		StorageValue<_, Vec<Option<DifficultyAndTimestamp<T::Moment>>>, ValueQuery>;
+
+	/// Current difficulty.
+	#[pallet::storage]
+	#[pallet::getter(fn difficulty)]
+	pub type CurrentDifficulty<T> = StorageValue<_, Difficulty, ValueQuery>;
+
+	/// Initial difficulty.
+	#[pallet::storage]
+	pub type InitialDifficulty<T> = StorageValue<_, Difficulty, ValueQuery>;
+
+	/// Lower bound of the difficulty set by governance, `MIN_DIFFICULTY` if unset.
+	#[pallet::storage]
+	pub(super) type MinDifficulty<T> = StorageValue<_, Difficulty>;
+
+	/// Upper bound of the difficulty set by governance, `MAX_DIFFICULTY` if unset.
+	#[pallet::storage]
+	pub(super) type MaxDifficulty<T> = StorageValue<_, Difficulty>;
+
+	/// Target block time set by governance, `Config::TargetBlockTime` if unset.
+	#[pallet::storage]
+	pub(super) type TargetBlockTime<T: Config> = StorageValue<_, T::Moment>;
+
+	#[pallet::genesis_config]
+	#[cfg_attr(feature = "std", derive(Default))]
+	pub struct GenesisConfig {
+		/// Difficulty of the first blocks.
+		pub initial_difficulty: Difficulty,
+	}
 
-		for i in 1..data.len() {
-			data[i - 1] = data[i];
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			InitialDifficulty::<T>::put(self.initial_difficulty);
+			CurrentDifficulty::<T>::put(self.initial_difficulty);
 		}
+	}
 
-		data[data.len() - 1] = Some(DifficultyAndTimestamp {
-			timestamp: now,
-			difficulty: Self::difficulty(),
-		});
-
-		let mut ts_delta = 0;
-		for i in 1..(DIFFICULTY_ADJUST_WINDOW as usize) {
-			let prev: Option<u128> = data[i - 1].map(|d| d.timestamp.unique_saturated_into());
-			let cur: Option<u128> = data[i].map(|d| d.timestamp.unique_saturated_into());
-
-			let delta = match (prev, cur) {
-				(Some(prev), Some(cur)) => cur.saturating_sub(prev),
-				_ => block_time.into(),
-			};
-			ts_delta += delta;
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Difficulty has been retargeted.
+		Retargeted(Difficulty),
+		/// Difficulty has been overridden by governance.
+		DifficultyOverridden(Difficulty),
+		/// Difficulty bounds have been changed, as (min, max).
+		DifficultyBoundsChanged(Difficulty, Difficulty),
+		/// Target block time has been changed, in milliseconds.
+		TargetBlockTimeChanged(T::Moment),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Bounds are outside `MIN_DIFFICULTY` and `MAX_DIFFICULTY`, or min exceeds max.
+		InvalidBounds,
+		/// Difficulty is outside the current bounds.
+		DifficultyOutOfBounds,
+		/// Target block time is zero.
+		ZeroBlockTime,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		fn integrity_test() {
+			assert!(T::DifficultyWindow::get() >= 2, "Difficulty window must be at least 2 blocks");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the difficulty bounds within `MIN_DIFFICULTY` and `MAX_DIFFICULTY`. `None`
+		/// restores the default bound. The current difficulty is clamped right away.
+		#[pallet::weight(T::WeightInfo::set_difficulty_bounds())]
+		pub fn set_difficulty_bounds(
+			origin: OriginFor<T>,
+			min: Option<Difficulty>,
+			max: Option<Difficulty>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let lo = min.unwrap_or_else(|| U256::from(MIN_DIFFICULTY));
+			let hi = max.unwrap_or_else(|| U256::from(MAX_DIFFICULTY));
+			ensure!(
+				U256::from(MIN_DIFFICULTY) <= lo && lo <= hi && hi <= U256::from(MAX_DIFFICULTY),
+				Error::<T>::InvalidBounds
+			);
+
+			MinDifficulty::<T>::set(min);
+			MaxDifficulty::<T>::set(max);
+			Self::deposit_event(Event::DifficultyBoundsChanged(lo, hi));
+
+			let difficulty = Self::difficulty();
+			let clamped = difficulty.max(lo).min(hi);
+			if clamped != difficulty {
+				CurrentDifficulty::<T>::put(clamped);
+				Self::deposit_event(Event::DifficultyOverridden(clamped));
+			}
+
+			Ok(())
+		}
+
+		/// Force the difficulty of the next block, e.g. to recover from a hashrate collapse.
+		///
+		/// The retarget window is cleared and its empty slots count as blocks found on target
+		/// at the new difficulty, so the retarget starts over from it.
+		#[pallet::weight(T::WeightInfo::reset_difficulty())]
+		pub fn reset_difficulty(origin: OriginFor<T>, difficulty: Difficulty) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let (min, max) = Self::difficulty_bounds();
+			ensure!(min <= difficulty && difficulty <= max, Error::<T>::DifficultyOutOfBounds);
+
+			PastDifficultiesAndTimestamps::<T>::kill();
+			InitialDifficulty::<T>::put(difficulty);
+			CurrentDifficulty::<T>::put(difficulty);
+			Self::deposit_event(Event::DifficultyOverridden(difficulty));
+
+			Ok(())
 		}
 
-		if ts_delta == 0 {
-			ts_delta = 1;
+		/// Set the target block time in milliseconds. `None` restores `Config::TargetBlockTime`.
+		#[pallet::weight(T::WeightInfo::set_target_block_time())]
+		pub fn set_target_block_time(
+			origin: OriginFor<T>,
+			block_time: Option<T::Moment>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(block_time.map_or(true, |t| !t.is_zero()), Error::<T>::ZeroBlockTime);
+
+			TargetBlockTime::<T>::set(block_time);
+			Self::deposit_event(Event::TargetBlockTimeChanged(Self::target_block_time()));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Difficulty bounds in effect, as (min, max).
+	pub fn difficulty_bounds() -> (Difficulty, Difficulty) {
+		(
+			MinDifficulty::<T>::get().unwrap_or_else(|| U256::from(MIN_DIFFICULTY)),
+			MaxDifficulty::<T>::get().unwrap_or_else(|| U256::from(MAX_DIFFICULTY)),
+		)
+	}
+	/// Target block time in effect, in milliseconds.
+	pub fn target_block_time() -> T::Moment {
+		TargetBlockTime::<T>::get().unwrap_or_else(T::TargetBlockTime::get)
+	}
+
+	/// Difficulty and timestamp of each block of the retarget window, from earliest to latest.
+	pub fn difficulty_window() -> Vec<Option<DifficultyAndTimestamp<T::Moment>>> {
+		PastDifficultiesAndTimestamps::<T>::get()
+	}
+
+	/// Checks the difficulty state: the current difficulty is within bounds, and the window
+	/// is filled from the latest slot backwards with timestamps that never go back in time.
+	#[cfg(feature = "try-runtime")]
+	pub fn check_state() -> Result<(), &'static str> {
+		let (min, max) = Self::difficulty_bounds();
+		let difficulty = Self::difficulty();
+		if difficulty < min || difficulty > max {
+			return Err("Current difficulty is out of bounds")
 		}
 
-		let mut diff_sum = U256::zero();
-		for i in 0..(DIFFICULTY_ADJUST_WINDOW as usize) {
-			let diff = match data[i].map(|d| d.difficulty) {
-				Some(diff) => diff,
-				None => InitialDifficulty::get(),
-			};
-			diff_sum += diff;
+		let data = PastDifficultiesAndTimestamps::<T>::get();
+		let filled = data.iter().skip_while(|d| d.is_none());
+		let mut prev: Option<T::Moment> = None;
+		for d in filled {
+			let d = (*d).ok_or("Empty slot after a filled one in the difficulty window")?;
+			if prev.map_or(false, |prev| d.timestamp < prev) {
+				return Err("Timestamps of the difficulty window go back in time")
+			}
+			prev = Some(d.timestamp);
 		}
 
-		if diff_sum < U256::from(MIN_DIFFICULTY) {
-			diff_sum = U256::from(MIN_DIFFICULTY);
+		Ok(())
+	}
+}
+
+impl<T: Config> OnTimestampSet<T::Moment> for Pallet<T> {
+	fn on_timestamp_set(now: T::Moment) {
+		let block_time =
+			UniqueSaturatedInto::<u128>::unique_saturated_into(Self::target_block_time());
+
+		let window = T::DifficultyWindow::get() as usize;
+
+		let mut data = PastDifficultiesAndTimestamps::<T>::get();
+		data.push(Some(DifficultyAndTimestamp {
+			timestamp: now,
+			difficulty: Self::difficulty(),
+		}));
+
+		// Keep the latest `window` blocks, padded with empty slots on the earliest side, so
+		// that the window follows `DifficultyWindow` across runtime upgrades.
+		let len = data.len();
+		if len > window {
+			data.drain(..len - window);
+		} else if len < window {
+			let mut padded = vec![None; window - len];
+			padded.append(&mut data);
+			data = padded;
 		}
 
-		// adjust time delta toward goal subject to dampening and clamping
-		let adj_ts = clamp(
-			damp(ts_delta, block_time_window, DIFFICULTY_DAMP_FACTOR),
-			block_time_window,
-			CLAMP_FACTOR,
-		);
-
-		// minimum difficulty avoids getting stuck due to dampening
-		let difficulty = min(
-			U256::from(MAX_DIFFICULTY),
-			max(
-				U256::from(MIN_DIFFICULTY),
-				diff_sum * U256::from(block_time) / U256::from(adj_ts),
-			),
-		);
-
-		<PastDifficultiesAndTimestamps<T>>::put(data);
-		<CurrentDifficulty>::put(difficulty);
+		let (min, max) = Self::difficulty_bounds();
+		let difficulty = T::DifficultyAdjustment::next_difficulty(
+			&data,
+			InitialDifficulty::<T>::get(),
+			block_time,
+		)
+		.max(min)
+		.min(max);
+
+		PastDifficultiesAndTimestamps::<T>::put(data);
+		CurrentDifficulty::<T>::put(difficulty);
+		Self::deposit_event(Event::Retargeted(difficulty));
 	}
 }
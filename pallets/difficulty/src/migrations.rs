@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of 3DPass.
+//
+// Copyright (c) 2022 3DPass.
+//
+// 3DPass is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// 3DPass is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Config, DifficultyAndTimestamp, Pallet, PastDifficultiesAndTimestamps, STORAGE_VERSION};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, GetStorageVersion},
+	weights::Weight,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Storage version kept by the pallet in its own `StorageVersion` item, before it moved to the
+/// FRAME pallet storage version. Version 1 of the latter has the layout of `V1`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum StorageVersion {
+	/// The window is a fixed array of 60 blocks.
+	V0 = 0,
+	/// The window is a vector of `DifficultyWindow` blocks.
+	V1 = 1,
+}
+
+impl Default for StorageVersion {
+	fn default() -> Self {
+		StorageVersion::V0
+	}
+}
+
+impl StorageVersion {
+	pub fn migrate<T: Config>(self) -> StorageVersion {
+		match self {
+			StorageVersion::V0 => migrate_v0_to_v1::<T>(),
+			StorageVersion::V1 => (),
+		}
+
+		StorageVersion::V1
+	}
+}
+
+struct __LegacyStorageVersion;
+impl frame_support::traits::StorageInstance for __LegacyStorageVersion {
+	fn pallet_prefix() -> &'static str {
+		"Difficulty"
+	}
+	const STORAGE_PREFIX: &'static str = "StorageVersion";
+}
+
+type LegacyStorageVersion = frame_support::storage::types::StorageValue<
+	__LegacyStorageVersion,
+	StorageVersion,
+	frame_support::storage::types::ValueQuery,
+>;
+
+/// Migrate the storage from the legacy version to `STORAGE_VERSION`.
+pub fn migrate<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return T::DbWeight::get().reads(1)
+	}
+
+	LegacyStorageVersion::take().migrate::<T>();
+	STORAGE_VERSION.put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(3, 3)
+}
+
+struct __PastDifficultiesAndTimestampsV0;
+impl frame_support::traits::StorageInstance for __PastDifficultiesAndTimestampsV0 {
+	fn pallet_prefix() -> &'static str {
+		"Difficulty"
+	}
+	const STORAGE_PREFIX: &'static str = "PastDifficultiesAndTimestamps";
+}
+
+#[allow(type_alias_bounds)]
+type PastDifficultiesAndTimestampsV0<T: Config> = frame_support::storage::types::StorageValue<
+	__PastDifficultiesAndTimestampsV0,
+	[Option<DifficultyAndTimestamp<T::Moment>>; 60],
+>;
+
+fn migrate_v0_to_v1<T: Config>() {
+	// `on_timestamp_set` resizes the window to `DifficultyWindow` on the next block.
+	if let Some(data) = PastDifficultiesAndTimestampsV0::<T>::take() {
+		PastDifficultiesAndTimestamps::<T>::put(data.to_vec());
+	}
+}
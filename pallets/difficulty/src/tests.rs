@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of 3DPass.
+//
+// Copyright (c) 2022 3DPass.
+//
+// 3DPass is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// 3DPass is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the difficulty pallet and adjustment algorithms.
+
+use crate::mock::{self, new_test_ext, Origin, System, Test, INITIAL_DIFFICULTY};
+use crate::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	traits::{ConstU32, GetStorageVersion},
+};
+
+const BLOCK_TIME: u64 = 60_000;
+
+type AsertHour = Asert<ConstU32<60>>;
+
+/// Mines `blocks` blocks per `(blocks, hashes per second)` step, each taking
+/// `difficulty / hashrate` seconds, and returns the block times in milliseconds.
+fn simulate<A: DifficultyAdjustment<u64>>(
+	window: usize,
+	initial_difficulty: u128,
+	steps: &[(u32, u128)],
+) -> Vec<u64> {
+	let initial_difficulty = U256::from(initial_difficulty);
+	let mut data: Vec<Option<DifficultyAndTimestamp<u64>>> = vec![None; window];
+	let mut difficulty = initial_difficulty;
+	let mut timestamp = 0u64;
+	let mut block_times = Vec::new();
+
+	for (blocks, hashrate) in steps {
+		for _ in 0..*blocks {
+			let block_time = (difficulty.low_u128() * 1000 / hashrate).max(1) as u64;
+			timestamp += block_time;
+			block_times.push(block_time);
+
+			data.remove(0);
+			data.push(Some(DifficultyAndTimestamp { difficulty, timestamp }));
+			difficulty = A::next_difficulty(&data, initial_difficulty, BLOCK_TIME as u128);
+
+			assert!(difficulty >= U256::from(MIN_DIFFICULTY));
+		}
+	}
+
+	block_times
+}
+
+fn mean(block_times: &[u64]) -> f64 {
+	block_times.iter().sum::<u64>() as f64 / block_times.len() as f64 / BLOCK_TIME as f64
+}
+
+/// After each shock the block time settles back within 5% of target within a phase.
+fn settles_after_hashrate_shocks<A: DifficultyAdjustment<u64>>() {
+	const PHASE: usize = 600;
+	let steps = [(600, 1_000), (600, 10_000), (600, 1_000), (600, 100), (600, 1_000)];
+
+	for window in [30, 60] {
+		let block_times = simulate::<A>(window, 60_000, &steps);
+		for phase in block_times.chunks(PHASE) {
+			let settled = mean(&phase[PHASE - 100..]);
+			assert!(
+				(0.95..1.05).contains(&settled),
+				"window {}: block time settled at {} of target",
+				window,
+				settled,
+			);
+		}
+	}
+}
+
+/// A large miner joining and leaving over and over moves the mean block time by less
+/// than half of the target.
+fn holds_block_time_when_miners_come_and_go<A: DifficultyAdjustment<u64>>() {
+	for window in [30, 60] {
+		for period in [30, 100, 300] {
+			let steps: Vec<_> =
+				(0..3000 / period / 2).flat_map(|_| [(period, 1_000), (period, 10_000)]).collect();
+			let block_times = simulate::<A>(window, 60_000, &steps);
+			let mean = mean(&block_times);
+			assert!(
+				(0.5..1.5).contains(&mean),
+				"window {}, period {}: mean block time {} of target",
+				window,
+				period,
+				mean,
+			);
+		}
+	}
+}
+
+/// Forged timestamps and extreme difficulties never take the difficulty out of bounds.
+fn stays_within_bounds<A: DifficultyAdjustment<u64>>() {
+	let windows = [
+		// all blocks at once
+		vec![
+			Some(DifficultyAndTimestamp { difficulty: U256::from(MAX_DIFFICULTY), timestamp: 0 });
+			60
+		],
+		// a block far in the future
+		(0..60)
+			.map(|i| Some(DifficultyAndTimestamp {
+				difficulty: U256::from(MIN_DIFFICULTY),
+				timestamp: if i == 59 { u64::max_value() } else { i * BLOCK_TIME },
+			}))
+			.collect(),
+		// a block back in time
+		(0..60)
+			.map(|i| Some(DifficultyAndTimestamp {
+				difficulty: U256::from(1_000_000),
+				timestamp: if i == 59 { 0 } else { 1_000_000 + i * BLOCK_TIME },
+			}))
+			.collect(),
+		vec![None; 60],
+		vec![None; 1],
+	];
+
+	for data in &windows {
+		let difficulty = A::next_difficulty(data, U256::from(1_000), BLOCK_TIME as u128);
+		assert!(difficulty >= U256::from(MIN_DIFFICULTY));
+		assert!(difficulty <= U256::from(MAX_DIFFICULTY));
+	}
+}
+
+fn on_target(difficulty: u128) -> Vec<Option<DifficultyAndTimestamp<u64>>> {
+	(0..60)
+		.map(|i| {
+			Some(DifficultyAndTimestamp { difficulty: U256::from(difficulty), timestamp: i * BLOCK_TIME })
+		})
+		.collect()
+}
+
+#[test]
+fn moving_average_settles_after_hashrate_shocks() {
+	settles_after_hashrate_shocks::<MovingAverage>();
+}
+
+#[test]
+fn lwma_settles_after_hashrate_shocks() {
+	settles_after_hashrate_shocks::<Lwma>();
+}
+
+#[test]
+fn asert_settles_after_hashrate_shocks() {
+	settles_after_hashrate_shocks::<AsertHour>();
+}
+
+#[test]
+fn moving_average_holds_block_time_when_miners_come_and_go() {
+	holds_block_time_when_miners_come_and_go::<MovingAverage>();
+}
+
+#[test]
+fn lwma_holds_block_time_when_miners_come_and_go() {
+	holds_block_time_when_miners_come_and_go::<Lwma>();
+}
+
+#[test]
+fn asert_holds_block_time_when_miners_come_and_go() {
+	holds_block_time_when_miners_come_and_go::<AsertHour>();
+}
+
+#[test]
+fn algorithms_stay_within_bounds() {
+	stays_within_bounds::<MovingAverage>();
+	stays_within_bounds::<Lwma>();
+	stays_within_bounds::<AsertHour>();
+}
+
+#[test]
+fn lwma_and_asert_keep_difficulty_on_target() {
+	let data = on_target(1_000_000);
+	let initial = U256::from(1_000);
+
+	assert_eq!(Lwma::next_difficulty(&data, initial, BLOCK_TIME as u128), U256::from(1_000_000));
+	assert_eq!(
+		AsertHour::next_difficulty(&data, initial, BLOCK_TIME as u128),
+		U256::from(1_000_000),
+	);
+}
+
+#[test]
+fn asert_halves_difficulty_a_half_life_behind_schedule() {
+	let mut data = on_target(1_000_000);
+	// the latest block took an hour longer than scheduled
+	data[59].as_mut().unwrap().timestamp += 60 * BLOCK_TIME;
+
+	assert_eq!(
+		AsertHour::next_difficulty(&data, U256::from(1_000), BLOCK_TIME as u128),
+		U256::from(500_000),
+	);
+}
+
+fn last_event() -> Event<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			mock::Event::Difficulty(e) => Some(e),
+			_ => None,
+		})
+		.last()
+		.expect("Event expected")
+}
+
+/// Sets the timestamp of one block per `timestamps`, as `pallet_timestamp` does.
+fn mine(timestamps: impl IntoIterator<Item = u64>) {
+	for timestamp in timestamps {
+		Pallet::<Test>::on_timestamp_set(timestamp);
+	}
+}
+
+#[test]
+fn retarget_emits_event() {
+	new_test_ext().execute_with(|| {
+		mine([mock::BLOCK_TIME]);
+
+		assert_eq!(Pallet::<Test>::difficulty(), U256::from(INITIAL_DIFFICULTY));
+		assert_eq!(last_event(), Event::Retargeted(U256::from(INITIAL_DIFFICULTY)));
+
+		let window = Pallet::<Test>::difficulty_window();
+		assert_eq!(window.len(), mock::WINDOW as usize);
+		assert_eq!(
+			window.last(),
+			Some(&Some(DifficultyAndTimestamp {
+				difficulty: U256::from(INITIAL_DIFFICULTY),
+				timestamp: mock::BLOCK_TIME,
+			})),
+		);
+	});
+}
+
+#[test]
+fn bounds_clamp_current_and_retargeted_difficulty() {
+	new_test_ext().execute_with(|| {
+		let min = U256::from(2 * INITIAL_DIFFICULTY);
+
+		assert_ok!(Pallet::<Test>::set_difficulty_bounds(Origin::root(), Some(min), None));
+		assert_eq!(Pallet::<Test>::difficulty_bounds(), (min, U256::from(MAX_DIFFICULTY)));
+		assert_eq!(Pallet::<Test>::difficulty(), min);
+		assert_eq!(last_event(), Event::DifficultyOverridden(min));
+
+		// blocks on target at the initial difficulty would retarget below the bound
+		mine((1..=4).map(|i| i * mock::BLOCK_TIME));
+		assert_eq!(Pallet::<Test>::difficulty(), min);
+
+		assert_ok!(Pallet::<Test>::set_difficulty_bounds(Origin::root(), None, None));
+		assert_eq!(
+			last_event(),
+			Event::DifficultyBoundsChanged(U256::from(MIN_DIFFICULTY), U256::from(MAX_DIFFICULTY)),
+		);
+	});
+}
+
+#[test]
+fn invalid_bounds_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let lo = Some(U256::from(1_000));
+		let hi = Some(U256::from(2_000));
+
+		assert_noop!(Pallet::<Test>::set_difficulty_bounds(Origin::signed(1), lo, hi), BadOrigin);
+		assert_noop!(
+			Pallet::<Test>::set_difficulty_bounds(Origin::root(), hi, lo),
+			Error::<Test>::InvalidBounds,
+		);
+		assert_noop!(
+			Pallet::<Test>::set_difficulty_bounds(Origin::root(), Some(U256::from(MIN_DIFFICULTY - 1)), hi),
+			Error::<Test>::InvalidBounds,
+		);
+	});
+}
+
+#[test]
+fn reset_restarts_retarget_from_new_difficulty() {
+	new_test_ext().execute_with(|| {
+		// a second per block drives the difficulty up
+		mine((1..=8).map(|i| i * 1_000));
+		assert!(Pallet::<Test>::difficulty() > U256::from(INITIAL_DIFFICULTY));
+
+		let difficulty = U256::from(5_000);
+		assert_noop!(Pallet::<Test>::reset_difficulty(Origin::signed(1), difficulty), BadOrigin);
+		assert_noop!(
+			Pallet::<Test>::reset_difficulty(Origin::root(), U256::from(MIN_DIFFICULTY - 1)),
+			Error::<Test>::DifficultyOutOfBounds,
+		);
+
+		assert_ok!(Pallet::<Test>::reset_difficulty(Origin::root(), difficulty));
+		assert_eq!(Pallet::<Test>::difficulty(), difficulty);
+		assert_eq!(last_event(), Event::DifficultyOverridden(difficulty));
+
+		// the cleared window does not pull the difficulty back up
+		mine([8_000 + mock::BLOCK_TIME]);
+		assert_eq!(Pallet::<Test>::difficulty(), difficulty);
+	});
+}
+
+#[test]
+fn target_block_time_can_be_changed() {
+	new_test_ext().execute_with(|| {
+		let block_time = 2 * mock::BLOCK_TIME;
+
+		assert_noop!(Pallet::<Test>::set_target_block_time(Origin::signed(1), Some(block_time)), BadOrigin);
+		assert_noop!(
+			Pallet::<Test>::set_target_block_time(Origin::root(), Some(0)),
+			Error::<Test>::ZeroBlockTime,
+		);
+
+		assert_ok!(Pallet::<Test>::set_target_block_time(Origin::root(), Some(block_time)));
+		assert_eq!(Pallet::<Test>::target_block_time(), block_time);
+		assert_eq!(last_event(), Event::TargetBlockTimeChanged(block_time));
+
+		// blocks at the former target are now too fast
+		mine((1..=4).map(|i| i * mock::BLOCK_TIME));
+		assert!(Pallet::<Test>::difficulty() > U256::from(INITIAL_DIFFICULTY));
+
+		assert_ok!(Pallet::<Test>::set_target_block_time(Origin::root(), None));
+		assert_eq!(Pallet::<Test>::target_block_time(), mock::BLOCK_TIME);
+	});
+}
+
+#[test]
+fn legacy_window_is_migrated() {
+	new_test_ext().execute_with(|| {
+		let mut window = [None; 60];
+		window[59] = Some(DifficultyAndTimestamp {
+			difficulty: U256::from(INITIAL_DIFFICULTY),
+			timestamp: mock::BLOCK_TIME,
+		});
+		frame_support::storage::unhashed::put(
+			&PastDifficultiesAndTimestamps::<Test>::hashed_key(),
+			&window,
+		);
+		StorageVersion::new(0).put::<Pallet<Test>>();
+
+		migrations::migrate::<Test>();
+
+		assert_eq!(Pallet::<Test>::difficulty_window(), window.to_vec());
+		assert_eq!(Pallet::<Test>::on_chain_storage_version(), STORAGE_VERSION);
+	});
+}
@@ -17,10 +17,10 @@ benchmarks! {
 			child_root: None,
 		});
 
-		frame_system::Module::<T>::set_block_number(40u32.into());
+		frame_system::Pallet::<T>::set_block_number(40u32.into());
 	}: _(RawOrigin::Signed(caller), campaign)
 
-	remove_expired_campaign {
+	remove_expired_child_storage {
 		let caller = whitelisted_caller();
 		let campaign = [b't', b'e', b's', b't'];
 
@@ -30,7 +30,7 @@ benchmarks! {
 			child_root: None,
 		});
 
-		frame_system::Module::<T>::set_block_number(40u32.into());
+		frame_system::Pallet::<T>::set_block_number(40u32.into());
 	}: _(RawOrigin::Signed(caller), campaign)
 
 	lock {
@@ -54,6 +54,6 @@ benchmarks! {
 			child_root: None,
 		});
 
-		frame_system::Module::<T>::set_block_number(40u32.into());
+		frame_system::Pallet::<T>::set_block_number(40u32.into());
 	}: _(RawOrigin::Signed(caller), campaign)
 }
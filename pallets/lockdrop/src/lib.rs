@@ -18,6 +18,10 @@
 // along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
 
 //! Era information recording.
+//!
+//! The pallet was moved from `decl_storage!`, which stored it under the `Eras` prefix. A
+//! runtime with existing lockdrop storage has to name the pallet `Eras` in
+//! `construct_runtime!` to keep reading it.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,20 +32,23 @@ mod benchmarking;
 mod default_weights;
 
 use codec::{Encode, Decode};
-use frame_benchmarking::StateVersion;
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use sp_std::{cmp, prelude::*};
 use sp_std::convert::TryInto;
-use sp_runtime::{RuntimeDebug, traits::Hash};
+use sp_runtime::{RuntimeDebug, StateVersion, traits::Hash};
 use frame_support::{
-	ensure, decl_storage, decl_module, decl_event, decl_error, storage::child,
-	traits::{Currency, LockableCurrency, WithdrawReasons, LockIdentifier, Get},
+	ensure, storage::child,
+	traits::{Currency, LockableCurrency, WithdrawReasons, LockIdentifier, Get, StorageVersion},
 	weights::Weight,
 };
-use frame_system::{ensure_root, ensure_signed};
 use scale_info::TypeInfo;
 
+pub use pallet::*;
+
+/// The current storage version.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 pub trait WeightInfo {
 	fn create_campaign() -> Weight;
 	fn conclude_campaign() -> Weight;
@@ -75,33 +82,92 @@ pub struct ChildLockData<T: Config> {
 	payload: Option<Vec<u8>>,
 }
 
-pub trait Config: frame_system::Config {
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-	/// An implementation of on-chain currency.
-	type Currency: LockableCurrency<Self::AccountId>;
+/// Type alias for currency balance.
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
-	/// Payload length limit.
-	type PayloadLenLimit: Get<u32>;
-	/// Max number of storage keys to remove per extrinsic call.
-	type RemoveKeysLimit: Get<u32>;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// An implementation of on-chain currency.
+		type Currency: LockableCurrency<Self::AccountId>;
+
+		/// Payload length limit.
+		#[pallet::constant]
+		type PayloadLenLimit: Get<u32>;
+		/// Max number of storage keys to remove per extrinsic call.
+		#[pallet::constant]
+		type RemoveKeysLimit: Get<u32>;
+
+		/// Weights for this pallet.
+		type WeightInfo: WeightInfo;
+	}
 
-	/// Weights for this pallet.
-	type WeightInfo: WeightInfo;
-}
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn campaigns)]
+	pub type Campaigns<T: Config> = StorageMap<_, Blake2_128Concat, CampaignIdentifier, CampaignInfo<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	pub type Locks<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, CampaignIdentifier,
+		Blake2_128Concat, T::AccountId,
+		LockInfo<T>,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Campaigns open from genesis, as (identifier, end block, minimum lock end block).
+		pub campaigns: Vec<(CampaignIdentifier, T::BlockNumber, T::BlockNumber)>,
+	}
 
-/// Type alias for currency balance.
-pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { campaigns: Vec::new() }
+		}
+	}
 
-decl_storage! {
-	trait Store for Module<T: Config> as Eras {
-		Campaigns get(fn campaigns): map hasher(blake2_128_concat) CampaignIdentifier => Option<CampaignInfo<T>>;
-		Locks get(fn locks): double_map hasher(blake2_128_concat) CampaignIdentifier, hasher(blake2_128_concat) T::AccountId => Option<LockInfo<T>>;
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (identifier, end_block, min_lock_end_block) in &self.campaigns {
+				assert!(min_lock_end_block > end_block, "Campaign lock must end after the campaign");
+				Campaigns::<T>::insert(identifier, CampaignInfo {
+					end_block: *end_block,
+					min_lock_end_block: *min_lock_end_block,
+					child_root: None,
+				});
+			}
+		}
 	}
-}
 
-decl_error! {
-	pub enum Error for Module<T: Config> {
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		CampaignCreated(CampaignIdentifier),
+		CampaignConcluded(CampaignIdentifier, Vec<u8>),
+		ChildStorageRemoved(CampaignIdentifier),
+		ChildStoragePartiallyRemoved(CampaignIdentifier),
+		Locked(CampaignIdentifier, T::AccountId),
+		Unlocked(CampaignIdentifier, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
 		/// The given campaign name was used in the past.
 		CampaignIdentifierUsedInPast,
 		/// The given campaign trying to create has already existed.
@@ -123,27 +189,29 @@ decl_error! {
 		/// Invalid lock end block.
 		InvalidLockEndBlock,
 	}
-}
 
-decl_event! {
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
-		CampaignCreated(CampaignIdentifier),
-		CampaignConcluded(CampaignIdentifier, Vec<u8>),
-		ChildStorageRemoved(CampaignIdentifier),
-		ChildStoragePartiallyRemoved(CampaignIdentifier),
-		Locked(CampaignIdentifier, AccountId),
-		Unlocked(CampaignIdentifier, AccountId),
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// The storage layout is unchanged from `decl_storage!`, only the version is new.
+			if Self::on_chain_storage_version() < STORAGE_VERSION {
+				STORAGE_VERSION.put::<Self>();
+				T::DbWeight::get().reads_writes(1, 1)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
 	}
-}
-
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
 
-		fn deposit_event() = default;
-
-		#[weight = T::WeightInfo::create_campaign()]
-		fn create_campaign(origin, identifier: CampaignIdentifier, end_block: T::BlockNumber, min_lock_end_block: T::BlockNumber) {
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::create_campaign())]
+		pub fn create_campaign(
+			origin: OriginFor<T>,
+			identifier: CampaignIdentifier,
+			end_block: T::BlockNumber,
+			min_lock_end_block: T::BlockNumber,
+		) -> DispatchResult {
 			ensure_root(origin)?;
 
 			let campaign_name_used_in_past = Locks::<T>::iter_prefix_values(identifier).next().is_some();
@@ -157,10 +225,12 @@ decl_module! {
 
 			Campaigns::<T>::insert(identifier, CampaignInfo { end_block, min_lock_end_block, child_root: None });
 			Self::deposit_event(Event::<T>::CampaignCreated(identifier));
+
+			Ok(())
 		}
 
-		#[weight = T::WeightInfo::conclude_campaign()]
-		fn conclude_campaign(origin, identifier: CampaignIdentifier) {
+		#[pallet::weight(T::WeightInfo::conclude_campaign())]
+		pub fn conclude_campaign(origin: OriginFor<T>, identifier: CampaignIdentifier) -> DispatchResult {
 			ensure_signed(origin)?;
 
 			Campaigns::<T>::mutate(&identifier, |info| {
@@ -175,10 +245,12 @@ decl_module! {
 					}
 				}
 			});
+
+			Ok(())
 		}
 
-		#[weight = T::WeightInfo::remove_expired_child_storage()]
-		fn remove_expired_child_storage(origin, identifier: CampaignIdentifier) {
+		#[pallet::weight(T::WeightInfo::remove_expired_child_storage())]
+		pub fn remove_expired_child_storage(origin: OriginFor<T>, identifier: CampaignIdentifier) -> DispatchResult {
 			ensure_signed(origin)?;
 
 			let info = Campaigns::<T>::get(&identifier);
@@ -195,10 +267,18 @@ decl_module! {
 					}
 				}
 			}
+
+			Ok(())
 		}
 
-		#[weight = T::WeightInfo::lock()]
-		fn lock(origin, amount: BalanceOf<T>, identifier: CampaignIdentifier, lock_end_block: T::BlockNumber, payload: Option<Vec<u8>>) {
+		#[pallet::weight(T::WeightInfo::lock())]
+		pub fn lock(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+			identifier: CampaignIdentifier,
+			lock_end_block: T::BlockNumber,
+			payload: Option<Vec<u8>>,
+		) -> DispatchResult {
 			let account_id = ensure_signed(origin)?;
 
 			ensure!(T::Currency::free_balance(&account_id) >= amount, Error::<T>::NotEnoughBalance);
@@ -232,10 +312,12 @@ decl_module! {
 
 			Locks::<T>::insert(identifier, account_id.clone(), lock_info);
 			Self::deposit_event(Event::<T>::Locked(identifier, account_id));
+
+			Ok(())
 		}
 
-		#[weight = T::WeightInfo::unlock()]
-		fn unlock(origin, identifier: CampaignIdentifier) {
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(origin: OriginFor<T>, identifier: CampaignIdentifier) -> DispatchResult {
 			let account_id = ensure_signed(origin)?;
 
 			let info = Locks::<T>::get(&identifier, &account_id);
@@ -250,11 +332,13 @@ decl_module! {
 					Self::deposit_event(Event::<T>::Unlocked(identifier, account_id));
 				}
 			}
+
+			Ok(())
 		}
 	}
 }
 
-impl<T: Config> Module<T> {
+impl<T: Config> Pallet<T> {
 	pub fn lock_identifier(identifier: CampaignIdentifier) -> LockIdentifier {
 		[b'd', b'r', b'o', b'p', identifier[0], identifier[1], identifier[2], identifier[3]]
 	}
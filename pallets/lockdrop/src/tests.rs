@@ -3,7 +3,7 @@ use super::*;
 use crate as pallet_lockdrop;
 use frame_support::{
 	assert_noop, assert_ok, assert_storage_noop, parameter_types,
-	traits::{Everything, OnFinalize, OnInitialize},
+	traits::{ConstU32, Everything, OnFinalize, OnInitialize},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -24,7 +24,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Lockdrop: pallet_lockdrop::{Pallet, Call, Storage, Event<T>},
+		Lockdrop: pallet_lockdrop::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -58,6 +58,7 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
 }
 
 parameter_types! {
@@ -95,6 +96,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		balances: pallet_balances::GenesisConfig {
 			balances: vec![(1, 1000), (2, 2000)],
 		},
+		lockdrop: Default::default(),
 	}
 	.build_storage()
 	.unwrap();
@@ -183,3 +185,23 @@ fn create_campaign_lock_works() {
 		assert_eq!(Balances::usable_balance(1), 1000);
 	})
 }
+
+#[test]
+fn genesis_campaigns_are_created() {
+	let t = GenesisConfig {
+		system: Default::default(),
+		balances: Default::default(),
+		lockdrop: pallet_lockdrop::GenesisConfig {
+			campaigns: vec![(TEST_CAMPAIGN, 20, 30)],
+		},
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(
+			Lockdrop::campaigns(TEST_CAMPAIGN),
+			Some(CampaignInfo { end_block: 20, min_lock_end_block: 30, child_root: None })
+		);
+	});
+}
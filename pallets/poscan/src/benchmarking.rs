@@ -1,20 +1,174 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking for the PoScan pallet.
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as PoScan;
+use crate::inherents::MinedObjectData;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
 use frame_system::RawOrigin;
+use pallet_uniques::BenchmarkHelper;
+use sp_runtime::traits::{Bounded, One, StaticLookup};
+use sp_std::prelude::*;
+
+fn hashes(n: u32, seed: u32) -> Vec<H256> {
+	(0..n).map(|i| H256::from_low_u64_be(((seed as u64) << 32) | i as u64)).collect()
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, DepositBalanceOf::<T>::max_value() / 2u32.into());
+}
+
+// Commits `who` to `hashes` and moves to the next block, so that they can be registered.
+fn commit<T: Config>(who: &T::AccountId, hashes: &[H256]) {
+	fund::<T>(who);
+	let commitment = PoScan::<T>::object_commitment(who, hashes);
+	PoScan::<T>::commit_object(RawOrigin::Signed(who.clone()).into(), commitment)
+		.expect("commitment is made");
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now + One::one());
+}
+
+fn register<T: Config>(owner: &T::AccountId, h: u32) -> H256 {
+	let hashes = hashes(h, 0);
+	let object = hashes[0];
+	commit::<T>(owner, &hashes);
+	PoScan::<T>::register_object(RawOrigin::Signed(owner.clone()).into(), hashes, vec![0; 32])
+		.expect("object registers");
+	object
+}
+
+// Creates the NFT collection, free holding so that minting reserves nothing.
+fn create_collection<T: Config>() -> T::CollectionId {
+	let collection = T::Helper::collection(0);
+	let owner: T::AccountId = account("collection", 0, 0);
+	pallet_uniques::Pallet::<T>::force_create(
+		RawOrigin::Root.into(),
+		collection,
+		T::Lookup::unlookup(owner),
+		true,
+	).expect("collection is created");
+	NftCollection::<T>::put(collection);
+	collection
+}
+
+fn mint<T: Config>(owner: &T::AccountId, object: H256) -> T::ItemId {
+	let item = T::Helper::item(0);
+	PoScan::<T>::mint_object_nft(RawOrigin::Signed(owner.clone()).into(), object, item)
+		.expect("NFT is minted");
+	item
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	// Worst case: the object has `MaxObjectHashes` hashes, all truncated to the bound.
+	set_mined_object {
+		frame_system::Pallet::<T>::set_block_number(2u32.into());
+		let object = MinedObjectData {
+			alg_id: sp_consensus_poscan::POSCAN_ALGO_GRID2D,
+			hashes: hashes(T::MaxObjectHashes::get(), 1),
+			obj_hash: Some(H256::repeat_byte(1)),
+		};
+	}: _(RawOrigin::None, object)
+	verify {
+		assert!(MinedObjects::<T>::contains_key(T::BlockNumber::from(1u32)));
+	}
+
+	register_object {
+		let h in 1 .. T::MaxObjectHashes::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let hashes = hashes(h, 0);
+		let metadata = vec![0; T::MaxMetadataLen::get() as usize];
+		commit::<T>(&caller, &hashes);
+	}: _(RawOrigin::Signed(caller), hashes.clone(), metadata)
+	verify {
+		assert!(Objects::<T>::contains_key(hashes[0]));
+	}
+
+	// Worst case: the object has an NFT, which is transferred with it.
+	transfer_object {
+		let caller: T::AccountId = whitelisted_caller();
+		let dest: T::AccountId = account("dest", 0, 0);
+		create_collection::<T>();
+		let object = register::<T>(&caller, 1);
+		mint::<T>(&caller, object);
+	}: _(RawOrigin::Signed(caller), object, dest.clone())
+	verify {
+		assert_eq!(PoScan::<T>::object_owner(&object), Some(dest));
+	}
+
+	// Worst case: the object has an NFT, which is burnt.
+	revoke_object {
+		let h in 1 .. T::MaxObjectHashes::get();
+		let caller: T::AccountId = whitelisted_caller();
+		create_collection::<T>();
+		let object = register::<T>(&caller, h);
+		mint::<T>(&caller, object);
+	}: _(RawOrigin::Signed(caller), object)
+	verify {
+		assert!(!Objects::<T>::contains_key(object));
+	}
+
+	set_nft_collection {
+		let collection = T::Helper::collection(0);
+	}: _(RawOrigin::Root, collection)
+	verify {
+		assert_eq!(NftCollection::<T>::get(), Some(collection));
+	}
+
+	mint_object_nft {
+		let h in 1 .. T::MaxObjectHashes::get();
+		let caller: T::AccountId = whitelisted_caller();
+		create_collection::<T>();
+		let object = register::<T>(&caller, h);
+		let item = T::Helper::item(0);
+	}: _(RawOrigin::Signed(caller), object, item)
+	verify {
+		assert!(ObjectNft::<T>::contains_key(object));
+	}
+
+	sync_object_owner {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let dest: T::AccountId = account("dest", 0, 0);
+		let collection = create_collection::<T>();
+		let object = register::<T>(&owner, 1);
+		let item = mint::<T>(&owner, object);
+		pallet_uniques::Pallet::<T>::do_transfer(collection, item, dest.clone(), |_, _| Ok(()))
+			.expect("NFT is transferred");
+	}: _(RawOrigin::Signed(caller), object)
+	verify {
+		assert_eq!(Objects::<T>::get(object).map(|info| info.owner), Some(dest));
+	}
+
+	put_object {
+		let caller: T::AccountId = whitelisted_caller();
+	}: {
+		assert!(PoScan::<T>::put_object(RawOrigin::Signed(caller).into(), vec![0; 32]).is_err());
+	}
+
+	get_object_ext {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller))
+
+	commit_object {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let commitment = PoScan::<T>::object_commitment(&caller, &hashes(1, 0));
+	}: _(RawOrigin::Signed(caller.clone()), commitment)
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller, commitment));
+	}
+
+	cancel_commitment {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		let hashes = hashes(1, 0);
+		commit::<T>(&caller, &hashes);
+		let commitment = PoScan::<T>::object_commitment(&caller, &hashes);
+	}: _(RawOrigin::Signed(caller.clone()), commitment)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(!Commitments::<T>::contains_key(&caller, commitment));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(PoScan, crate::mock::new_test_ext(), crate::mock::Test);
 }
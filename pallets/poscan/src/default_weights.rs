@@ -0,0 +1,64 @@
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+	fn put_object() -> Weight {
+		(7_900_000 as Weight)
+	}
+	fn set_mined_object() -> Weight {
+		(21_400_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn register_object(h: u32, ) -> Weight {
+		(52_300_000 as Weight)
+			.saturating_add((5_300_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn transfer_object() -> Weight {
+		(61_200_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn revoke_object(h: u32, ) -> Weight {
+		(82_400_000 as Weight)
+			.saturating_add((3_100_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn set_nft_collection() -> Weight {
+		(12_700_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn mint_object_nft(h: u32, ) -> Weight {
+		(83_500_000 as Weight)
+			.saturating_add((210_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn sync_object_owner() -> Weight {
+		(29_600_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn get_object_ext() -> Weight {
+		(8_200_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+	}
+	fn commit_object() -> Weight {
+		(30_100_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_commitment() -> Weight {
+		(28_700_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+}
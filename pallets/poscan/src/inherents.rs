@@ -1,46 +1,81 @@
-use codec::Decode;
-use sp_inherents::{InherentIdentifier, InherentData};
+//! `p3d` inherent: the object mined in the parent block.
+//!
+//! The object of a block is only known once the block is sealed, after its body is built, so
+//! the runtime learns about it one block later. The block author reads the object hashes from
+//! the post-digests of the parent header and supplies them with this inherent. On import the
+//! same data is derived from the (already verified) parent header, so `check_inherent` ties the
+//! recorded object to the parent's seal.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_inherents::{InherentIdentifier, IsFatalError};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 // This needs to be unique for the runtime.
-const INHERENT_IDENTIFIER: InherentIdentifier = *b"p3d";
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"p3d_objt";
+
+/// Object mined in a block, as carried by its header.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MinedObjectData {
+	/// Algorithm the object hashes were computed with.
+	pub alg_id: [u8; 16],
+	/// p3d hashes of the object.
+	pub hashes: Vec<H256>,
+	/// Blake2-256 of the (decompressed) object, if the provider supplies it.
+	pub obj_hash: Option<H256>,
+}
+
+/// Errors of the `p3d` inherent.
+#[derive(Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub enum InherentError {
+	/// The object in the block differs from the one in the parent header.
+	ObjectMismatch,
+	/// The block records an object but the parent header carries none.
+	UnexpectedObject,
+	/// The parent header carries an object but the block does not record it.
+	MissingObject,
+	/// The block seals an object that has already been mined.
+	DuplicateObject,
+}
 
-/// Some custom inherent data provider
-struct InherentDataProvider;
+impl IsFatalError for InherentError {
+	fn is_fatal_error(&self) -> bool {
+		true
+	}
+}
 
+/// Provides the object mined in the parent block.
+#[cfg(feature = "std")]
+pub struct InherentDataProvider(pub Option<MinedObjectData>);
+
+#[cfg(feature = "std")]
 #[async_trait::async_trait]
 impl sp_inherents::InherentDataProvider for InherentDataProvider {
-    fn provide_inherent_data(
-        &self,
-        inherent_data: &mut InherentData,
-    ) -> Result<(), sp_inherents::Error> {
-        // We can insert any data that implements [`codec::Encode`].
-        inherent_data.put_data(INHERENT_IDENTIFIER, &"hello")
-    }
-
-    /// When validating the inherents, the runtime implementation can throw errors. We support
-    /// two error modes, fatal and non-fatal errors. A fatal error means that the block is invalid
-    /// and this function here should return `Err(_)` to not import the block. Non-fatal errors
-    /// are allowed to be handled here in this function and the function should return `Ok(())`
-    /// if it could be handled. A non-fatal error is for example that a block is in the future
-    /// from the point of view of the local node. In such a case the block import for example
-    /// should be delayed until the block is valid.
-    ///
-    /// If this functions returns `None`, it means that it is not responsible for this error or
-    /// that the error could not be interpreted.
-    async fn try_handle_error(
-        &self,
-        identifier: &InherentIdentifier,
-        mut error: &[u8],
-    ) -> Option<Result<(), sp_inherents::Error>> {
-        // Check if this error belongs to us.
-        if *identifier != INHERENT_IDENTIFIER {
-            return None;
-        }
-
-        // For demonstration purposes we are using a `String` as error type. In real
-        // implementations it is advised to not use `String`.
-        Some(Err(
-            sp_inherents::Error::Application(Box::from(String::decode(&mut error).ok()?))
-        ))
-    }
-}
\ No newline at end of file
+	fn provide_inherent_data(
+		&self,
+		inherent_data: &mut sp_inherents::InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		match &self.0 {
+			Some(data) => inherent_data.put_data(INHERENT_IDENTIFIER, data),
+			// genesis has no object
+			None => Ok(()),
+		}
+	}
+
+	async fn try_handle_error(
+		&self,
+		identifier: &InherentIdentifier,
+		mut error: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		// Check if this error belongs to us.
+		if *identifier != INHERENT_IDENTIFIER {
+			return None;
+		}
+
+		let error = InherentError::decode(&mut error).ok()?;
+		Some(Err(sp_inherents::Error::Application(Box::from(format!("{:?}", error)))))
+	}
+}
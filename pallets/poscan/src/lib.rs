@@ -5,10 +5,18 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 extern crate alloc;
-use core::num::ParseIntError;
-use sp_std::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::Currency,
+	weights::Weight,
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_core::H256;
 // use frame_support::debug::info;
 
+pub mod inherents;
+
 #[cfg(test)]
 mod mock;
 
@@ -17,40 +25,183 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod default_weights;
 
-fn decode_hex(buf: &[u8]) -> Result<Vec<u8>, ParseIntError> {
-	let s = core::str::from_utf8(buf).unwrap();
-	(0..s.len())
-		.step_by(2)
-		.map(|i| u8::from_str_radix(&s[i..i + 2], 16))
-		.collect()
+pub trait WeightInfo {
+	fn put_object() -> Weight;
+	fn set_mined_object() -> Weight;
+	fn register_object(h: u32) -> Weight;
+	fn transfer_object() -> Weight;
+	fn revoke_object(h: u32) -> Weight;
+	fn set_nft_collection() -> Weight;
+	fn mint_object_nft(h: u32) -> Weight;
+	fn sync_object_owner() -> Weight;
+	fn get_object_ext() -> Weight;
+	fn commit_object() -> Weight;
+	fn cancel_commitment() -> Weight;
+}
+
+/// Balance of the currency object deposits are reserved in.
+pub type DepositBalanceOf<T> = <<T as pallet_uniques::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// A 3D object registered on chain.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ObjectInfo<T: Config> {
+	/// Current owner of the object.
+	pub owner: T::AccountId,
+	/// Block the object was registered at.
+	pub when: T::BlockNumber,
+	/// p3d hashes of the object. The first one identifies the object.
+	pub hashes: BoundedVec<H256, T::MaxObjectHashes>,
+	/// Free-form metadata supplied by the owner.
+	pub metadata: BoundedVec<u8, T::MaxMetadataLen>,
+	/// Account the deposit is reserved from, the account that registered the object.
+	pub depositor: T::AccountId,
+	/// Deposit reserved for the object, released when it is revoked.
+	pub deposit: DepositBalanceOf<T>,
+}
+
+/// Object mined in a block, recorded by the `p3d` inherent of the next block.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct MinedObject<T: Config> {
+	/// Algorithm the object hashes were computed with.
+	pub alg_id: [u8; 16],
+	/// p3d hashes of the object.
+	pub hashes: BoundedVec<H256, T::MaxObjectHashes>,
+	/// Blake2-256 of the object, if known.
+	pub obj_hash: Option<H256>,
 }
 
 #[frame_support::pallet]
 pub mod pallet {
 	// use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{tokens::nonfungibles::Inspect, ReservableCurrency},
+	};
 	use sp_std::vec::Vec;
-	// use frame_support::sp_runtime::print as prn;
-	// use frame_support::runtime_print;
-	// use crate::{DEQUE, MiningProposal};
-	use super::decode_hex;
+	use sp_core::H256;
+	use sp_runtime::traits::{Hash, Saturating, Zero};
+	use super::{DepositBalanceOf, ObjectInfo, MinedObject, WeightInfo};
+	use crate::inherents::{InherentError, MinedObjectData, INHERENT_IDENTIFIER};
+	use sp_consensus_poscan::SEALED_OBJECT_INHERENT_IDENTIFIER;
+
+	/// Attribute of an object NFT holding the p3d hashes of the object.
+	pub const NFT_HASHES_KEY: &[u8] = b"p3d";
+
+	/// Maximum number of blocks `recent_objects` looks back.
+	pub const MAX_RECENT_OBJECTS: u32 = 256;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
+	///
+	/// Registered objects can be minted as NFTs of `pallet_uniques`, in the collection
+	/// set with `set_nft_collection`. Object deposits are reserved in the `pallet_uniques`
+	/// currency.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + pallet_uniques::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Maximum number of p3d hashes of a registered object.
+		#[pallet::constant]
+		type MaxObjectHashes: Get<u32>;
+
+		/// Maximum length of the metadata of a registered object.
+		#[pallet::constant]
+		type MaxMetadataLen: Get<u32>;
+
+		/// Weights for this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Number of blocks mined objects are kept for, as served by `recent_objects`.
+		#[pallet::constant]
+		type MinedObjectsDepth: Get<u32>;
+
+		/// Number of blocks the primary hashes of mined objects are kept for. A block may not
+		/// reuse the primary hash of an object mined within this many blocks.
+		#[pallet::constant]
+		type MinedHashesDepth: Get<u32>;
+
+		/// Deposit reserved for a commitment to register an object, and kept as part of the
+		/// deposit of the object.
+		#[pallet::constant]
+		type ObjectDepositBase: Get<DepositBalanceOf<Self>>;
+
+		/// Deposit reserved per byte of p3d hashes and metadata of a registered object.
+		#[pallet::constant]
+		type ObjectDepositPerByte: Get<DepositBalanceOf<Self>>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
-	// The pallet's runtime storage items.
-	// #[pallet::storage]
-	// pub(super) type Proofs<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, (T::AccountId, T::BlockNumber), ValueQuery>;
+	/// Registered objects, by their first p3d hash.
+	#[pallet::storage]
+	#[pallet::getter(fn objects)]
+	pub type Objects<T: Config> = StorageMap<_, Blake2_128Concat, H256, ObjectInfo<T>>;
+
+	/// First p3d hash of the registered object each p3d hash belongs to.
+	#[pallet::storage]
+	#[pallet::getter(fn object_of_hash)]
+	pub type ObjectOfHash<T: Config> = StorageMap<_, Blake2_128Concat, H256, H256>;
+
+	/// Objects mined in past blocks, by block number.
+	#[pallet::storage]
+	#[pallet::getter(fn mined_object)]
+	pub type MinedObjects<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, MinedObject<T>>;
+
+	/// Block each kept primary hash of a mined object was mined in.
+	#[pallet::storage]
+	#[pallet::getter(fn mined_hash)]
+	pub type MinedHashes<T: Config> = StorageMap<_, Blake2_128Concat, H256, T::BlockNumber>;
+
+	/// Primary hash claimed in `MinedHashes` by the object mined in a block, by block number,
+	/// so that it can be removed once the block is `MinedHashesDepth` old.
+	#[pallet::storage]
+	pub(super) type MinedHashAt<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, H256>;
+
+	/// Whether the mined object was recorded in the current block.
+	#[pallet::storage]
+	pub(super) type DidRecordObject<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Collection of `pallet_uniques` new object NFTs are minted in.
+	#[pallet::storage]
+	#[pallet::getter(fn nft_collection)]
+	pub type NftCollection<T: Config> = StorageValue<_, T::CollectionId>;
+
+	/// Collection and item of the NFT minted for a registered object.
+	#[pallet::storage]
+	#[pallet::getter(fn object_nft)]
+	pub type ObjectNft<T: Config> = StorageMap<_, Blake2_128Concat, H256, (T::CollectionId, T::ItemId)>;
+
+	/// Registered object an NFT was minted for.
+	#[pallet::storage]
+	#[pallet::getter(fn nft_object)]
+	pub type NftObject<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, T::CollectionId,
+		Blake2_128Concat, T::ItemId,
+		H256,
+	>;
+
+	/// Commitments to register objects, with the block they were made at and the deposit
+	/// reserved for them. See `object_commitment`.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	pub type Commitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		Blake2_128Concat, H256,
+		(T::BlockNumber, DepositBalanceOf<T>),
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -61,6 +212,28 @@ pub mod pallet {
 		ClaimCreated(T::AccountId, Vec<u8>),
 		/// Event emitted when a claim is revoked by the owner. [who, claim]
 		GetMiningObject(Vec<u8>),
+		/// An object has been registered. [owner, object]
+		ObjectRegistered(T::AccountId, H256),
+		/// An object has been transferred. [object, from, to]
+		ObjectTransferred(H256, T::AccountId, T::AccountId),
+		/// An object has been revoked by its owner. [owner, object]
+		ObjectRevoked(T::AccountId, H256),
+		/// The NFT collection has been set. [collection]
+		NftCollectionSet(T::CollectionId),
+		/// An NFT has been minted for an object. [object, item]
+		ObjectNftMinted(H256, T::ItemId),
+		/// The object mined in a block has been recorded. [block, first p3d hash]
+		ObjectMined(T::BlockNumber, H256),
+		/// A commitment to register an object has been made. [who, commitment]
+		ObjectCommitted(T::AccountId, H256),
+		/// A commitment has been cancelled and its deposit released. [who, commitment]
+		CommitmentCancelled(T::AccountId, H256),
+		/// The NFT of an object has been burnt through `pallet_uniques`, so the object has
+		/// been removed. [object]
+		ObjectBurned(H256),
+		/// The object mined in a block had already been mined in a kept block, so it has
+		/// been recorded without claiming its primary hash. [block, first p3d hash]
+		DuplicateObjectMined(T::BlockNumber, H256),
 	}
 
 	// Errors inform users that something went wrong.
@@ -72,66 +245,72 @@ pub mod pallet {
 		NoSuchProof,
 		/// The proof is claimed by another account, so caller can't revoke it.
 		NotProofOwner,
+		/// No p3d hashes were given.
+		NoHashes,
+		/// More p3d hashes than `MaxObjectHashes` were given.
+		TooManyHashes,
+		/// Metadata is longer than `MaxMetadataLen`.
+		MetadataTooLong,
+		/// The NFT collection has not been set.
+		NoNftCollection,
+		/// An NFT has already been minted for the object.
+		NftAlreadyMinted,
+		/// The p3d hashes do not fit in an NFT attribute.
+		NftAttributeTooLong,
+		/// The NFT owner already owns the object.
+		AlreadySynced,
+		/// The mined object has already been recorded in this block.
+		ObjectAlreadyRecorded,
+		/// The call is no longer supported, use `register_object`.
+		Deprecated,
+		/// No commitment to register the object was made with `commit_object`.
+		NoCommitment,
+		/// The commitment was made in the current block.
+		CommitmentTooRecent,
+		/// The commitment has already been made.
+		CommitmentExists,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight = 0;
+			if let Some(old) = Self::expired_block(n, T::MinedObjectsDepth::get()) {
+				MinedObjects::<T>::remove(old);
+				weight += T::DbWeight::get().writes(1);
+			}
+			if let Some(old) = Self::expired_block(n, T::MinedHashesDepth::get()) {
+				weight += match MinedHashAt::<T>::take(old) {
+					Some(hash) => {
+						MinedHashes::<T>::remove(hash);
+						T::DbWeight::get().reads_writes(1, 2)
+					},
+					None => T::DbWeight::get().reads(1),
+				};
+			}
+			weight
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			DidRecordObject::<T>::kill();
+		}
+	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsics", which are often compared to transactions.
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(1_000_000_000)]
+		/// Superseded by `register_object`, kept so that the indices of the calls do not change.
+		#[pallet::weight(T::WeightInfo::put_object())]
 		pub fn put_object(
 			_origin: OriginFor<T>,
-			proof: Vec<u8>,
+			_proof: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://docs.substrate.io/v3/runtime/origins
-			// let sender = ensure_signed(origin)?;
-
-			// Verify that the specified proof has not already been claimed.
-			// ensure!(!Proofs::<T>::contains_key(&proof), Error::<T>::ProofAlreadyClaimed);
-
-			// Get the block number from the FRAME System pallet.
-			// let _current_block = <frame_system::Pallet<T>>::block_number();
-
-			let _content = decode_hex(&proof).unwrap();
-
-			// let mut buf: Vec<u8>;
-			// let res = p3d::p3d_process(&content, p3d::AlgoType::Grid2d, 6i16, 2i16 );
-			// match res {
-			// 	Ok(v) => {
-			// 		for i in 0..v.len()-1 {
-			// 			prn(v[i].as_str());
-			// 		}
-			// 		buf = v.concat().as_bytes().to_vec();
-			// 	},
-			// 	Err(_) => {
-			// 		runtime_print!(">>> Error");
-			// 		return Err(DispatchError::Other(&"Error in p3d"));
-			// 	},
-			// }
-			//
-			// buf.extend(&proof);
-			// Store the proof with the sender and block number.
-			// Proofs::<T>::insert(&buf, (&sender, current_block, ));
-
-			// prn("put_minig_obj recieved object");
-			//
-			// let mut lock = DEQUE.lock();
-			// (*lock).push_back(MiningProposal {a: 1, pre_obj: content});
-
-
-			// Emit an event that the claim was created.
-			// Self::deposit_event(Event::ClaimCreated(sender, proof));
-
-			Ok(().into())
+			Err(Error::<T>::Deprecated.into())
 		}
 
-		#[pallet::weight(1_000_000_000)]
+		#[pallet::weight(T::WeightInfo::get_object_ext())]
 		pub fn get_object_ext(
 			_origin: OriginFor<T>,
 		) -> DispatchResultWithPostInfo {
@@ -147,26 +326,410 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Record the object mined in the parent block.
+		///
+		/// This call should be invoked exactly once per block, after block 1, as an inherent.
+		/// Its data is checked against the parent header on import.
+		///
+		/// The call is mandatory, so it records objects without hashes and objects mined
+		/// before instead of failing. A block sealing an object mined before is rejected on
+		/// import by `check_inherent`. If such a block was imported anyway, the primary hash
+		/// stays with the block that mined it first and `DuplicateObjectMined` is emitted.
+		#[pallet::weight((T::WeightInfo::set_mined_object(), DispatchClass::Mandatory))]
+		pub fn set_mined_object(
+			origin: OriginFor<T>,
+			object: MinedObjectData,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			ensure!(!DidRecordObject::<T>::get(), Error::<T>::ObjectAlreadyRecorded);
+
+			let block = <frame_system::Pallet<T>>::block_number().saturating_sub(1u32.into());
+			let first = object.hashes.first().copied();
+			// hashes beyond the bound are not kept, the first ones identify the object
+			let mut hashes = object.hashes;
+			hashes.truncate(T::MaxObjectHashes::get() as usize);
+			let hashes: BoundedVec<H256, T::MaxObjectHashes> = hashes.try_into().unwrap_or_default();
+
+			MinedObjects::<T>::insert(block, MinedObject {
+				alg_id: object.alg_id,
+				hashes,
+				obj_hash: object.obj_hash,
+			});
+			DidRecordObject::<T>::put(true);
+
+			match first {
+				Some(first) if MinedHashes::<T>::contains_key(first) => {
+					Self::deposit_event(Event::DuplicateObjectMined(block, first));
+				},
+				Some(first) => {
+					MinedHashes::<T>::insert(first, block);
+					MinedHashAt::<T>::insert(block, first);
+					Self::deposit_event(Event::ObjectMined(block, first));
+				},
+				None => {},
+			}
+
+			Ok(().into())
+		}
+
+		/// Register an object by its p3d hashes. Fails if any of the hashes is already
+		/// registered.
+		///
+		/// The hashes must have been committed to with `commit_object` in an earlier block,
+		/// so that they cannot be registered first by someone who saw this call. A deposit
+		/// growing with the size of the hashes and metadata is reserved until the object is
+		/// revoked.
+		#[pallet::weight(T::WeightInfo::register_object(hashes.len() as u32))]
+		pub fn register_object(
+			origin: OriginFor<T>,
+			hashes: Vec<H256>,
+			metadata: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let commitment = Self::object_commitment(&sender, &hashes);
+			let (committed_at, reserved) = Commitments::<T>::get(&sender, commitment)
+				.ok_or(Error::<T>::NoCommitment)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(committed_at < now, Error::<T>::CommitmentTooRecent);
+
+			let hashes: BoundedVec<H256, T::MaxObjectHashes> =
+				hashes.try_into().map_err(|_| Error::<T>::TooManyHashes)?;
+			let metadata: BoundedVec<u8, T::MaxMetadataLen> =
+				metadata.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+			let object = *hashes.first().ok_or(Error::<T>::NoHashes)?;
+
+			for (i, hash) in hashes.iter().enumerate() {
+				ensure!(!ObjectOfHash::<T>::contains_key(hash), Error::<T>::ProofAlreadyClaimed);
+				ensure!(!hashes[..i].contains(hash), Error::<T>::ProofAlreadyClaimed);
+			}
+
+			let deposit = Self::object_deposit(hashes.len(), metadata.len());
+			if deposit > reserved {
+				T::Currency::reserve(&sender, deposit - reserved)?;
+			} else {
+				T::Currency::unreserve(&sender, reserved - deposit);
+			}
+			Commitments::<T>::remove(&sender, commitment);
+
+			for hash in hashes.iter() {
+				ObjectOfHash::<T>::insert(hash, object);
+			}
+			Objects::<T>::insert(object, ObjectInfo {
+				owner: sender.clone(),
+				when: now,
+				hashes,
+				metadata,
+				depositor: sender.clone(),
+				deposit,
+			});
+
+			Self::deposit_event(Event::ObjectRegistered(sender, object));
+
+			Ok(().into())
+		}
+
+		/// Transfer an owned object to another account, together with its NFT if one was
+		/// minted.
+		#[pallet::weight(T::WeightInfo::transfer_object())]
+		pub fn transfer_object(
+			origin: OriginFor<T>,
+			object: H256,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Objects::<T>::try_mutate(object, |info| -> DispatchResult {
+				let info = info.as_mut().ok_or(Error::<T>::NoSuchProof)?;
+				ensure!(Self::owner_of(&object, info) == Some(sender.clone()), Error::<T>::NotProofOwner);
+				if let Some((collection, item)) = Self::live_nft(&object) {
+					pallet_uniques::Pallet::<T>::do_transfer(collection, item, dest.clone(), |_, _| Ok(()))?;
+				}
+				info.owner = dest.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ObjectTransferred(object, sender, dest));
+
+			Ok(().into())
+		}
+
+		/// Revoke an owned object, which frees its hashes for registration, burns its NFT and
+		/// releases its deposit to the account that registered it.
+		#[pallet::weight(T::WeightInfo::revoke_object(T::MaxObjectHashes::get()))]
+		pub fn revoke_object(
+			origin: OriginFor<T>,
+			object: H256,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info = Objects::<T>::get(object).ok_or(Error::<T>::NoSuchProof)?;
+			ensure!(Self::owner_of(&object, &info) == Some(sender.clone()), Error::<T>::NotProofOwner);
+
+			if let Some((collection, item)) = Self::live_nft(&object) {
+				pallet_uniques::Pallet::<T>::do_burn(collection, item, |_, _| Ok(()))?;
+			}
+			Self::remove_object(&object, &info);
+
+			Self::deposit_event(Event::ObjectRevoked(sender, object));
+
+			Ok(Some(T::WeightInfo::revoke_object(info.hashes.len() as u32)).into())
+		}
+
+		/// Set the `pallet_uniques` collection object NFTs are minted in.
+		///
+		/// The collection should be created with `force_create` and free holding, so that
+		/// minting does not reserve item deposits from its owner.
+		#[pallet::weight(T::WeightInfo::set_nft_collection())]
+		pub fn set_nft_collection(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			NftCollection::<T>::put(collection);
+			Self::deposit_event(Event::NftCollectionSet(collection));
+
+			Ok(().into())
+		}
+
+		/// Mint an NFT for an owned object as `item` of the NFT collection. The p3d hashes
+		/// of the object are stored in the `p3d` attribute of the item.
+		///
+		/// From then on the owner of the NFT owns the object, so the NFT can be traded
+		/// with `pallet_uniques` calls.
+		#[pallet::weight(T::WeightInfo::mint_object_nft(T::MaxObjectHashes::get()))]
+		pub fn mint_object_nft(
+			origin: OriginFor<T>,
+			object: H256,
+			item: T::ItemId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let collection = NftCollection::<T>::get().ok_or(Error::<T>::NoNftCollection)?;
+			let info = Objects::<T>::get(object).ok_or(Error::<T>::NoSuchProof)?;
+			ensure!(Self::owner_of(&object, &info) == Some(sender.clone()), Error::<T>::NotProofOwner);
+			ensure!(!ObjectNft::<T>::contains_key(object), Error::<T>::NftAlreadyMinted);
+
+			let key: BoundedVec<u8, T::KeyLimit> = NFT_HASHES_KEY.to_vec()
+				.try_into()
+				.map_err(|_| Error::<T>::NftAttributeTooLong)?;
+			let value: BoundedVec<u8, T::ValueLimit> = info.hashes.iter()
+				.flat_map(|h| h.as_bytes().to_vec())
+				.collect::<Vec<u8>>()
+				.try_into()
+				.map_err(|_| Error::<T>::NftAttributeTooLong)?;
+
+			pallet_uniques::Pallet::<T>::do_mint(collection, item, sender, |_| Ok(()))?;
+			pallet_uniques::Pallet::<T>::set_attribute(
+				frame_system::RawOrigin::Root.into(),
+				collection,
+				Some(item),
+				key,
+				value,
+			)?;
+
+			ObjectNft::<T>::insert(object, (collection, item));
+			NftObject::<T>::insert(collection, item, object);
+
+			Self::deposit_event(Event::ObjectNftMinted(object, item));
+
+			Ok(Some(T::WeightInfo::mint_object_nft(info.hashes.len() as u32)).into())
+		}
+
+		/// Record the owner of the NFT of an object, after the NFT has been transferred
+		/// through `pallet_uniques`, as the owner of the object. Callable by anyone.
+		///
+		/// If the NFT has been burnt through `pallet_uniques` the object is removed as by
+		/// `revoke_object`.
+		#[pallet::weight(T::WeightInfo::sync_object_owner().max(
+			T::WeightInfo::revoke_object(T::MaxObjectHashes::get())
+		))]
+		pub fn sync_object_owner(
+			origin: OriginFor<T>,
+			object: H256,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let mut info = Objects::<T>::get(object).ok_or(Error::<T>::NoSuchProof)?;
+			let to = match Self::owner_of(&object, &info) {
+				Some(owner) => owner,
+				None => {
+					Self::remove_object(&object, &info);
+					Self::deposit_event(Event::ObjectBurned(object));
+					return Ok(Some(T::WeightInfo::revoke_object(info.hashes.len() as u32)).into())
+				},
+			};
+			ensure!(to != info.owner, Error::<T>::AlreadySynced);
+			let from = core::mem::replace(&mut info.owner, to.clone());
+			Objects::<T>::insert(object, info);
+
+			Self::deposit_event(Event::ObjectTransferred(object, from, to));
+
+			Ok(Some(T::WeightInfo::sync_object_owner()).into())
+		}
+
+		/// Commit to registering an object, see `object_commitment`. Reserves
+		/// `ObjectDepositBase` until the object is registered or the commitment is cancelled.
+		#[pallet::weight(T::WeightInfo::commit_object())]
+		pub fn commit_object(
+			origin: OriginFor<T>,
+			commitment: H256,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!Commitments::<T>::contains_key(&sender, commitment), Error::<T>::CommitmentExists);
+			let deposit = T::ObjectDepositBase::get();
+			T::Currency::reserve(&sender, deposit)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			Commitments::<T>::insert(&sender, commitment, (now, deposit));
+
+			Self::deposit_event(Event::ObjectCommitted(sender, commitment));
+
+			Ok(().into())
+		}
+
+		/// Cancel a commitment made with `commit_object`, releasing its deposit.
+		#[pallet::weight(T::WeightInfo::cancel_commitment())]
+		pub fn cancel_commitment(
+			origin: OriginFor<T>,
+			commitment: H256,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (_, deposit) = Commitments::<T>::take(&sender, commitment)
+				.ok_or(Error::<T>::NoCommitment)?;
+			T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(Event::CommitmentCancelled(sender, commitment));
+
+			Ok(().into())
+		}
 	}
-}
 
-// impl<T: Config> Pallet<T> {
-// 	pub fn get_mining_object() -> Vec<u8> {
-// 		let digest = <frame_system::Pallet<T>>::digest();
-// 		let ll: u8 = digest.logs.len() as u8;
-// 		// let item = digest.logs.last().unwrap();
-// 		// let mut obj = Vec::new();
-// 		//
-// 		// if let DigestItem::Other(v) = item {
-// 		// 	obj = v.to_vec();
-// 		// 	if obj[..4] == vec![b'l', b'z', b's', b's'] {
-// 		// 		obj = sp_consensus_poscan::decompress_obj(&obj[4..]);
-// 		// 	}
-// 		// }
-// 		// obj
-// 		let mut v = Vec::new();
-// 		// v.push(ll);
-// 		v
-// 	}
-// }
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = InherentError;
+		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			Self::inherent_object(data).map(|object| Call::set_mined_object { object })
+		}
+
+		fn check_inherent(call: &Self::Call, data: &InherentData) -> Result<(), Self::Error> {
+			let object = match call {
+				Call::set_mined_object { object } => object,
+				_ => return Ok(()),
+			};
+
+			match Self::inherent_object(data) {
+				Some(expected) if expected == *object => {},
+				Some(_) => return Err(InherentError::ObjectMismatch),
+				None => return Err(InherentError::UnexpectedObject),
+			}
+
+			// the object sealed in the block itself, supplied on import
+			let sealed = data.get_data::<Vec<H256>>(&SEALED_OBJECT_INHERENT_IDENTIFIER).ok().flatten();
+			if let Some(first) = sealed.as_ref().and_then(|hashes| hashes.first()) {
+				if MinedHashes::<T>::contains_key(first) || object.hashes.first() == Some(first) {
+					return Err(InherentError::DuplicateObject)
+				}
+			}
+
+			Ok(())
+		}
+
+		fn is_inherent_required(data: &InherentData) -> Result<Option<Self::Error>, Self::Error> {
+			Ok(Self::inherent_object(data).map(|_| InherentError::MissingObject))
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::set_mined_object { .. })
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn inherent_object(data: &InherentData) -> Option<MinedObjectData> {
+			data.get_data::<MinedObjectData>(&INHERENT_IDENTIFIER).ok().flatten()
+		}
+
+		/// Block whose mined data kept for `depth` blocks expires at block `n`, if any.
+		fn expired_block(n: T::BlockNumber, depth: u32) -> Option<T::BlockNumber> {
+			// the object recorded in block `n` is the one of block `n - 1`
+			let depth: T::BlockNumber = depth.into();
+			(n > depth).then(|| n - depth - 1u32.into())
+		}
 
+		/// Collection and item of the NFT of an object, if one was minted and has not been
+		/// burnt.
+		fn live_nft(object: &H256) -> Option<(T::CollectionId, T::ItemId)> {
+			let (collection, item) = ObjectNft::<T>::get(object)?;
+			pallet_uniques::Pallet::<T>::owner(&collection, &item).map(|_| (collection, item))
+		}
+
+		/// Owner of an object: the owner of its NFT if one was minted, the registered owner
+		/// otherwise. `None` if the NFT has been burnt through `pallet_uniques`, in which case
+		/// `sync_object_owner` removes the object.
+		pub(crate) fn owner_of(object: &H256, info: &ObjectInfo<T>) -> Option<T::AccountId> {
+			match ObjectNft::<T>::get(object) {
+				Some((collection, item)) => pallet_uniques::Pallet::<T>::owner(&collection, &item),
+				None => Some(info.owner.clone()),
+			}
+		}
+
+		/// Remove a registered object, freeing its hashes, its NFT links and its deposit.
+		fn remove_object(object: &H256, info: &ObjectInfo<T>) {
+			if let Some((collection, item)) = ObjectNft::<T>::take(object) {
+				NftObject::<T>::remove(collection, item);
+			}
+			for hash in info.hashes.iter() {
+				ObjectOfHash::<T>::remove(hash);
+			}
+			Objects::<T>::remove(object);
+			T::Currency::unreserve(&info.depositor, info.deposit);
+		}
+
+		/// Whether a block may not seal an object with this primary hash.
+		pub fn is_hash_used(hash: &H256) -> bool {
+			MinedHashes::<T>::contains_key(hash)
+		}
+
+		/// Objects mined in the last `count` blocks that are still kept, most recent first. At
+		/// most `MAX_RECENT_OBJECTS` blocks are looked at.
+		pub fn recent_objects(count: u32) -> Vec<(T::BlockNumber, Vec<H256>)> {
+			let count = count.min(T::MinedObjectsDepth::get()).min(MAX_RECENT_OBJECTS);
+			let mut block = <frame_system::Pallet<T>>::block_number();
+			let mut objects = Vec::new();
+			for _ in 0..count {
+				if block.is_zero() {
+					break
+				}
+				block -= 1u32.into();
+				if let Some(object) = MinedObjects::<T>::get(block) {
+					objects.push((block, object.hashes.into_inner()));
+				}
+			}
+			objects
+		}
+
+		/// Commitment of `who` to register an object with `hashes`, to be passed to
+		/// `commit_object` before calling `register_object`.
+		pub fn object_commitment(who: &T::AccountId, hashes: &[H256]) -> H256 {
+			sp_runtime::traits::BlakeTwo256::hash_of(&(who, hashes))
+		}
+
+		/// Deposit of an object with `hashes` p3d hashes and `metadata_len` bytes of metadata.
+		pub fn object_deposit(hashes: usize, metadata_len: usize) -> DepositBalanceOf<T> {
+			let bytes = (hashes * H256::len_bytes()).saturating_add(metadata_len) as u32;
+			T::ObjectDepositPerByte::get()
+				.saturating_mul(bytes.into())
+				.saturating_add(T::ObjectDepositBase::get())
+		}
+
+		/// Current owner of a registered object, `None` if its NFT has been burnt.
+		pub fn object_owner(object: &H256) -> Option<T::AccountId> {
+			Objects::<T>::get(object).and_then(|info| Self::owner_of(object, &info))
+		}
+	}
+}
@@ -1,20 +1,418 @@
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::inherents::{InherentError, MinedObjectData, INHERENT_IDENTIFIER};
+use sp_consensus_poscan::SEALED_OBJECT_INHERENT_IDENTIFIER;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResultWithPostInfo,
+	inherent::{InherentData, ProvideInherent},
+	traits::{tokens::nonfungibles::Inspect, Hooks, ReservableCurrency},
+};
+use sp_core::H256;
+
+fn hash(n: u8) -> H256 {
+	H256::repeat_byte(n)
+}
+
+// Commits `who` to registering `hashes` and moves to the next block.
+fn commit(who: u64, hashes: &[H256]) {
+	let commitment = TemplateModule::object_commitment(&who, hashes);
+	assert_ok!(TemplateModule::commit_object(Origin::signed(who), commitment));
+	System::set_block_number(System::block_number() + 1);
+}
+
+fn register(who: u64, hashes: Vec<H256>, metadata: Vec<u8>) -> DispatchResultWithPostInfo {
+	commit(who, &hashes);
+	TemplateModule::register_object(Origin::signed(who), hashes, metadata)
+}
+
+#[test]
+fn register_object_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(register(1, vec![hash(1), hash(2)], b"chair".to_vec()));
+
+		let info = TemplateModule::objects(hash(1)).unwrap();
+		assert_eq!(info.owner, 1);
+		assert_eq!(info.when, 6);
+		assert_eq!(info.hashes.to_vec(), vec![hash(1), hash(2)]);
+		assert_eq!(info.metadata.to_vec(), b"chair".to_vec());
+		assert_eq!(TemplateModule::object_of_hash(hash(2)), Some(hash(1)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectRegistered(1, hash(1))));
+	});
+}
+
+#[test]
+fn register_object_rejects_duplicates_and_bad_input() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, vec![hash(1), hash(2)], vec![]));
+
+		let reject = |hashes: Vec<H256>, metadata: Vec<u8>, error: Error<Test>| {
+			commit(2, &hashes);
+			assert_noop!(TemplateModule::register_object(Origin::signed(2), hashes, metadata), error);
+		};
+		// any hash of the set already registered
+		reject(vec![hash(3), hash(2)], vec![], Error::<Test>::ProofAlreadyClaimed);
+		// repeated within the set
+		reject(vec![hash(3), hash(3)], vec![], Error::<Test>::ProofAlreadyClaimed);
+		reject(vec![], vec![], Error::<Test>::NoHashes);
+		reject((3..8).map(hash).collect(), vec![], Error::<Test>::TooManyHashes);
+		reject(vec![hash(3)], vec![0; 17], Error::<Test>::MetadataTooLong);
+	});
+}
+
+#[test]
+fn register_object_requires_earlier_commitment() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let hashes = vec![hash(1), hash(2)];
+		assert_noop!(
+			TemplateModule::register_object(Origin::signed(1), hashes.clone(), vec![]),
+			Error::<Test>::NoCommitment
+		);
+
+		let commitment = TemplateModule::object_commitment(&1, &hashes);
+		assert_ok!(TemplateModule::commit_object(Origin::signed(1), commitment));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectCommitted(1, commitment)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_noop!(
+			TemplateModule::commit_object(Origin::signed(1), commitment),
+			Error::<Test>::CommitmentExists
+		);
+		assert_noop!(
+			TemplateModule::register_object(Origin::signed(1), hashes.clone(), vec![]),
+			Error::<Test>::CommitmentTooRecent
+		);
+
+		// the commitment of one account does not let another register the object
+		System::set_block_number(2);
+		assert_noop!(
+			TemplateModule::register_object(Origin::signed(2), hashes.clone(), vec![]),
+			Error::<Test>::NoCommitment
+		);
+		assert_ok!(TemplateModule::register_object(Origin::signed(1), hashes, vec![]));
+		assert_eq!(TemplateModule::commitment(1, commitment), None);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn cancel_commitment_releases_deposit() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		// events are not deposited at genesis
+		System::set_block_number(1);
+		let commitment = TemplateModule::object_commitment(&1, &[hash(1)]);
+		assert_ok!(TemplateModule::commit_object(Origin::signed(1), commitment));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_noop!(
+			TemplateModule::cancel_commitment(Origin::signed(2), commitment),
+			Error::<Test>::NoCommitment
+		);
+		assert_ok!(TemplateModule::cancel_commitment(Origin::signed(1), commitment));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(TemplateModule::commitment(1, commitment), None);
+		System::assert_last_event(Event::TemplateModule(crate::Event::CommitmentCancelled(1, commitment)));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn object_deposit_is_reserved_until_revoked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, vec![hash(1), hash(2)], b"chair".to_vec()));
+		// base and a byte for every byte of hashes and metadata
+		let deposit = 10 + 2 * 32 + 5;
+		assert_eq!(TemplateModule::object_deposit(2, 5), deposit);
+		assert_eq!(TemplateModule::objects(hash(1)).unwrap().deposit, deposit);
+		assert_eq!(Balances::reserved_balance(1), deposit);
+
+		// the deposit stays with the account that registered the object
+		assert_ok!(TemplateModule::transfer_object(Origin::signed(1), hash(1), 2));
+		assert_ok!(TemplateModule::revoke_object(Origin::signed(2), hash(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// the deposit must be affordable
+		let _ = Balances::reserve(&3, 990);
+		commit(3, &[hash(5)]);
+		assert_noop!(
+			TemplateModule::register_object(Origin::signed(3), vec![hash(5)], vec![]),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn put_object_is_deprecated() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::put_object(Origin::signed(1), vec![1]), Error::<Test>::Deprecated);
+	});
+}
+
+#[test]
+fn transfer_object_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register(1, vec![hash(1)], vec![]));
+
+		assert_noop!(
+			TemplateModule::transfer_object(Origin::signed(2), hash(1), 2),
+			Error::<Test>::NotProofOwner
+		);
+		assert_noop!(
+			TemplateModule::transfer_object(Origin::signed(1), hash(9), 2),
+			Error::<Test>::NoSuchProof
+		);
+
+		assert_ok!(TemplateModule::transfer_object(Origin::signed(1), hash(1), 2));
+		assert_eq!(TemplateModule::objects(hash(1)).unwrap().owner, 2);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectTransferred(hash(1), 1, 2)));
+	});
+}
+
+#[test]
+fn revoke_object_frees_hashes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register(1, vec![hash(1), hash(2)], vec![]));
+
+		assert_noop!(
+			TemplateModule::revoke_object(Origin::signed(2), hash(1)),
+			Error::<Test>::NotProofOwner
+		);
+
+		assert_ok!(TemplateModule::revoke_object(Origin::signed(1), hash(1)));
+		assert_eq!(TemplateModule::objects(hash(1)), None);
+		assert_eq!(TemplateModule::object_of_hash(hash(2)), None);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectRevoked(1, hash(1))));
+
+		assert_noop!(
+			TemplateModule::revoke_object(Origin::signed(1), hash(1)),
+			Error::<Test>::NoSuchProof
+		);
+		assert_ok!(register(2, vec![hash(2)], vec![]));
+	});
+}
+
+fn setup_nft_collection() {
+	assert_ok!(Uniques::force_create(Origin::root(), 0, 100, true));
+	assert_ok!(TemplateModule::set_nft_collection(Origin::root(), 0));
+}
+
+#[test]
+fn mint_object_nft_works() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		System::set_block_number(1);
+		assert_ok!(register(1, vec![hash(1), hash(2)], vec![]));
+
+		assert_noop!(
+			TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 7),
+			Error::<Test>::NoNftCollection
+		);
+		setup_nft_collection();
+		assert_noop!(
+			TemplateModule::mint_object_nft(Origin::signed(2), hash(1), 7),
+			Error::<Test>::NotProofOwner
+		);
+
+		assert_ok!(TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 7));
+		assert_eq!(Uniques::owner(&0, &7), Some(1));
+		let hashes = [hash(1).as_bytes(), hash(2).as_bytes()].concat();
+		assert_eq!(Uniques::attribute(&0, &7, crate::NFT_HASHES_KEY), Some(hashes));
+		assert_eq!(TemplateModule::object_nft(hash(1)), Some((0, 7)));
+		assert_eq!(TemplateModule::nft_object(0, 7), Some(hash(1)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectNftMinted(hash(1), 7)));
+
+		assert_noop!(
+			TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 8),
+			Error::<Test>::NftAlreadyMinted
+		);
+	});
+}
+
+#[test]
+fn object_and_nft_ownership_stay_in_sync() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_nft_collection();
+		assert_ok!(register(1, vec![hash(1)], vec![]));
+		assert_ok!(TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 7));
+
+		// transferring the object transfers the NFT
+		assert_ok!(TemplateModule::transfer_object(Origin::signed(1), hash(1), 2));
+		assert_eq!(Uniques::owner(&0, &7), Some(2));
+
+		// transferring the NFT transfers the object
+		assert_ok!(Uniques::transfer(Origin::signed(2), 0, 7, 3));
+		assert_eq!(TemplateModule::object_owner(&hash(1)), Some(3));
+		assert_noop!(
+			TemplateModule::transfer_object(Origin::signed(2), hash(1), 2),
+			Error::<Test>::NotProofOwner
+		);
+
+		assert_ok!(TemplateModule::sync_object_owner(Origin::signed(9), hash(1)));
+		assert_eq!(TemplateModule::objects(hash(1)).unwrap().owner, 3);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectTransferred(hash(1), 2, 3)));
+		assert_noop!(
+			TemplateModule::sync_object_owner(Origin::signed(9), hash(1)),
+			Error::<Test>::AlreadySynced
+		);
+
+		// revoking burns the NFT
+		assert_ok!(TemplateModule::revoke_object(Origin::signed(3), hash(1)));
+		assert_eq!(Uniques::owner(&0, &7), None);
+		assert_eq!(TemplateModule::nft_object(0, 7), None);
+	});
+}
+
+#[test]
+fn burning_the_nft_removes_the_object() {
+	new_test_ext().execute_with(|| {
+		setup_nft_collection();
+		assert_ok!(register(1, vec![hash(1), hash(2)], vec![]));
+		assert_ok!(TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 7));
+
+		// the registered owner does not own the object once the NFT is burnt
+		assert_ok!(Uniques::burn(Origin::signed(1), 0, 7, None));
+		assert_eq!(TemplateModule::object_owner(&hash(1)), None);
+		assert_noop!(
+			TemplateModule::transfer_object(Origin::signed(1), hash(1), 2),
+			Error::<Test>::NotProofOwner
+		);
+		assert_noop!(
+			TemplateModule::mint_object_nft(Origin::signed(1), hash(1), 8),
+			Error::<Test>::NotProofOwner
+		);
+
+		assert_ok!(TemplateModule::sync_object_owner(Origin::signed(9), hash(1)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectBurned(hash(1))));
+		assert_eq!(TemplateModule::objects(hash(1)), None);
+		assert_eq!(TemplateModule::object_of_hash(hash(2)), None);
+		assert_eq!(TemplateModule::object_nft(hash(1)), None);
+		assert_eq!(TemplateModule::nft_object(0, 7), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_ok!(register(2, vec![hash(2)], vec![]));
+	});
+}
+
+fn mined(first: u8) -> MinedObjectData {
+	MinedObjectData { alg_id: *b"grid2d-1.1      ", hashes: vec![hash(first), hash(first + 1)], obj_hash: Some(hash(99)) }
+}
+
+#[test]
+fn set_mined_object_records_parent_object() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			TemplateModule::set_mined_object(Origin::signed(1), mined(1)),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(1)));
+		let object = TemplateModule::mined_object(4).unwrap();
+		assert_eq!(object.hashes.to_vec(), vec![hash(1), hash(2)]);
+		assert_eq!(object.obj_hash, Some(hash(99)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectMined(4, hash(1))));
+
+		assert_noop!(
+			TemplateModule::set_mined_object(Origin::none(), mined(1)),
+			Error::<Test>::ObjectAlreadyRecorded
+		);
+	});
+}
+
+#[test]
+fn mined_object_inherent_is_checked_against_parent() {
+	new_test_ext().execute_with(|| {
+		let mut data = InherentData::new();
+		assert!(TemplateModule::create_inherent(&data).is_none());
+		assert!(matches!(
+			TemplateModule::check_inherent(&crate::Call::set_mined_object { object: mined(1) }, &data),
+			Err(InherentError::UnexpectedObject)
+		));
+
+		data.put_data(INHERENT_IDENTIFIER, &mined(1)).unwrap();
+		let call = TemplateModule::create_inherent(&data).unwrap();
+		assert!(TemplateModule::check_inherent(&call, &data).is_ok());
+		assert!(matches!(TemplateModule::is_inherent_required(&data), Ok(Some(InherentError::MissingObject))));
+		assert!(matches!(
+			TemplateModule::check_inherent(&crate::Call::set_mined_object { object: mined(3) }, &data),
+			Err(InherentError::ObjectMismatch)
+		));
+	});
+}
+
+#[test]
+fn mined_objects_cannot_be_reused_until_pruned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(1)));
+		assert_eq!(TemplateModule::mined_hash(hash(1)), Some(1));
+		TemplateModule::on_finalize(2);
+
+		// a block sealing an already mined object is rejected on import
+		let mut data = InherentData::new();
+		data.put_data(INHERENT_IDENTIFIER, &mined(5)).unwrap();
+		data.put_data(SEALED_OBJECT_INHERENT_IDENTIFIER, &vec![hash(1)]).unwrap();
+		let call = TemplateModule::create_inherent(&data).unwrap();
+		assert!(matches!(TemplateModule::check_inherent(&call, &data), Err(InherentError::DuplicateObject)));
+		// as is one sealing the object of its parent
+		data.replace_data(SEALED_OBJECT_INHERENT_IDENTIFIER, &vec![hash(5)]);
+		assert!(matches!(TemplateModule::check_inherent(&call, &data), Err(InherentError::DuplicateObject)));
+		data.replace_data(SEALED_OBJECT_INHERENT_IDENTIFIER, &vec![hash(7)]);
+		assert!(TemplateModule::check_inherent(&call, &data).is_ok());
+
+		// one imported without that check is recorded, but the hash stays with block 1
+		System::set_block_number(3);
+		TemplateModule::on_initialize(3);
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(1)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::DuplicateObjectMined(2, hash(1))));
+		assert!(TemplateModule::mined_object(2).is_some());
+		assert_eq!(TemplateModule::mined_hash(hash(1)), Some(1));
+		TemplateModule::on_finalize(3);
+
+		// objects are kept for `MinedObjectsDepth` blocks, their hashes for `MinedHashesDepth`
+		System::set_block_number(4);
+		TemplateModule::on_initialize(4);
+		assert_eq!(TemplateModule::mined_object(1), None);
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(40)));
+		TemplateModule::on_finalize(4);
+		assert_eq!(TemplateModule::mined_hash(hash(1)), Some(1));
+		System::set_block_number(5);
+		TemplateModule::on_initialize(5);
+		assert_eq!(TemplateModule::mined_hash(hash(1)), None);
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(1)));
+		System::assert_last_event(Event::TemplateModule(crate::Event::ObjectMined(4, hash(1))));
+	});
+}
+
+#[test]
+fn set_mined_object_records_objects_without_hashes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let object = MinedObjectData { alg_id: *b"grid2d-1.1      ", hashes: vec![], obj_hash: None };
+		assert_ok!(TemplateModule::set_mined_object(Origin::none(), object));
+		assert!(TemplateModule::mined_object(1).unwrap().hashes.is_empty());
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn recent_objects_lists_kept_objects() {
+	new_test_ext().execute_with(|| {
+		for n in 2..=4 {
+			System::set_block_number(n);
+			TemplateModule::on_initialize(n);
+			assert_ok!(TemplateModule::set_mined_object(Origin::none(), mined(n as u8 * 10)));
+			TemplateModule::on_finalize(n);
+		}
+
+		assert_eq!(
+			TemplateModule::recent_objects(2),
+			vec![(3, vec![hash(40), hash(41)]), (2, vec![hash(30), hash(31)])]
+		);
+		// the object of block 1 is pruned, its hash is still used
+		assert_eq!(TemplateModule::recent_objects(10).len(), 2);
+		assert!(TemplateModule::is_hash_used(&hash(20)));
+		assert!(!TemplateModule::is_hash_used(&hash(21)));
 	});
 }
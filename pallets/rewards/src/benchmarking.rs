@@ -23,11 +23,11 @@
 use super::*;
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::traits::{OnFinalize, OnInitialize};
-use frame_system::{DigestItemOf, EventRecord, RawOrigin};
-use sp_runtime::traits::Bounded;
+use frame_system::{EventRecord, RawOrigin};
+use sp_runtime::{traits::Bounded, DigestItem};
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
-	let events = frame_system::Module::<T>::events();
+	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::Event = generic_event.into();
 	// compare to the last event record
 	let EventRecord { event, .. } = &events[events.len() - 1];
@@ -46,26 +46,36 @@ fn create_locks<T: Config>(who: &T::AccountId, num_of_locks: u32) {
 	RewardLocks::<T>::insert(who, locks);
 }
 
+// This function ends the current round with `who` holding half of its points, so that
+// settling `who` pays part of the round rewards and keeps the rest.
+fn end_round_with_points<T: Config>(who: &T::AccountId) {
+	Pallet::<T>::note_participation(who, 1);
+	Pallet::<T>::note_participation(&account("validator", 1, 0), 1);
+	SessionValidatorRewards::<T>::put(T::Currency::minimum_balance().saturating_mul(2u32.into()));
+	Pallet::<T>::on_session_end(0);
+}
+
 benchmarks! {
 	// Worst case: Author info is in digest.
 	on_initialize {
 		let author: T::AccountId = account("author", 0, 0);
-		let author_digest = DigestItemOf::<T>::PreRuntime(sp_consensus_pow::POW_ENGINE_ID, author.encode());
-		frame_system::Module::<T>::deposit_log(author_digest);
+		let author_digest = DigestItem::PreRuntime(POSCAN_ENGINE_ID, author.encode());
+		frame_system::Pallet::<T>::deposit_log(author_digest);
 
 		Reward::<T>::put(T::Currency::minimum_balance());
 
 		// Whitelist transient storage items
 		frame_benchmarking::benchmarking::add_to_whitelist(Author::<T>::hashed_key().to_vec().into());
 
-		let block_number = frame_system::Module::<T>::block_number();
-	}: { crate::Module::<T>::on_initialize(block_number); }
+		let block_number = frame_system::Pallet::<T>::block_number();
+	}: { crate::Pallet::<T>::on_initialize(block_number); }
 	verify {
 		assert_eq!(Author::<T>::get(), Some(author));
 	}
 
 	// Worst case: This author already has `max_locks` locked up, produces a new block, and we unlock
-	// everything in addition to creating brand new locks for the new reward.
+	// everything in addition to creating brand new locks for the new reward. The reward is also
+	// shared with the validators and the treasury.
 	on_finalize {
 		let author: T::AccountId = account("author", 0, 0);
 		let reward = BalanceOf::<T>::max_value();
@@ -73,46 +83,160 @@ benchmarks! {
 		// Setup pallet variables
 		Author::<T>::put(&author);
 		Reward::<T>::put(reward);
+		RewardSplit::<T>::put(RewardShares {
+			miner: Perbill::from_percent(50),
+			validators: Perbill::from_percent(30),
+			treasury: Perbill::from_percent(20),
+		});
 
 		// Create existing locks on author.
 		let max_locks = T::GenerateRewardLocks::max_locks(T::LockParametersBounds::get());
 		create_locks::<T>(&author, max_locks);
 
 		// Move to a point where all locks would unlock.
-		frame_system::Module::<T>::set_block_number(max_locks.into());
+		frame_system::Pallet::<T>::set_block_number(max_locks.into());
 		assert_eq!(RewardLocks::<T>::get(&author).iter().count() as u32, max_locks);
 
 		// Whitelist transient storage items
 		frame_benchmarking::benchmarking::add_to_whitelist(Author::<T>::hashed_key().to_vec().into());
 
-		let block_number = frame_system::Module::<T>::block_number();
-	}: { crate::Module::<T>::on_finalize(block_number); }
+		let block_number = frame_system::Pallet::<T>::block_number();
+	}: { crate::Pallet::<T>::on_finalize(block_number); }
 	verify {
 		assert!(Author::<T>::get().is_none());
 		assert!(RewardLocks::<T>::get(&author).iter().count() > 0);
 	}
 
-	// Worst case: Target user has `max_locks` which are all unlocked during this call.
+	// Worst case: Caller has `max_locks` which are all unlocked during this call.
 	unlock {
-		let miner = account("miner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let max_locks = T::GenerateRewardLocks::max_locks(T::LockParametersBounds::get());
+		create_locks::<T>(&caller, max_locks);
+		frame_system::Pallet::<T>::set_block_number(max_locks.into());
+		assert_eq!(RewardLocks::<T>::get(&caller).iter().count() as u32, max_locks);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(RewardLocks::<T>::get(&caller).iter().count(), 0);
+	}
+
+	// Worst case: Caller already has `max_locks` and adds one more.
+	lock {
+		let caller: T::AccountId = whitelisted_caller();
 		let max_locks = T::GenerateRewardLocks::max_locks(T::LockParametersBounds::get());
-		create_locks::<T>(&miner, max_locks);
-		let caller = whitelisted_caller();
-		frame_system::Module::<T>::set_block_number(max_locks.into());
-		assert_eq!(RewardLocks::<T>::get(&miner).iter().count() as u32, max_locks);
-	}: _(RawOrigin::Signed(caller), miner.clone())
+		create_locks::<T>(&caller, max_locks);
+		let amount = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let when: T::BlockNumber = (max_locks + 1).into();
+	}: _(RawOrigin::Signed(caller.clone()), amount, when)
 	verify {
-		assert_eq!(RewardLocks::<T>::get(&miner).iter().count(), 0);
+		assert!(RewardLocks::<T>::get(&caller).contains_key(&when));
 	}
 
 	set_schedule {
 
-	}: _(RawOrigin::Root, T::Currency::minimum_balance(), BTreeMap::new(), BTreeMap::new(), BTreeMap::new())
+	}: _(RawOrigin::Root, T::Currency::minimum_balance(), Vec::new(), Vec::new(), Vec::new())
 
 	// Worst case: a new lock params is set.
 	set_lock_params {
 
 	}: _(RawOrigin::Root, LockParameters {period: 150, divide: 25} )
+
+	// Worst case: Caller has `max_locks` which are all unlocked while new locks are created
+	// for the claimed reward, and its points of an ended round are settled first.
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		let max_locks = T::GenerateRewardLocks::max_locks(T::LockParametersBounds::get());
+		create_locks::<T>(&caller, max_locks);
+		frame_system::Pallet::<T>::set_block_number(max_locks.into());
+		end_round_with_points::<T>(&caller);
+		let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		UnclaimedRewards::<T>::insert(&caller, amount);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(UnclaimedRewards::<T>::get(&caller).is_zero());
+		assert!(ValidatorPoints::<T>::get(&caller).is_none());
+		assert_last_event::<T>(
+			Event::<T>::RewardsClaimed(caller, amount + T::Currency::minimum_balance()).into()
+		);
+	}
+
+	// Worst case: The validator's points of an ended round are settled before the new ones
+	// are noted.
+	note_participation {
+		let validator: T::AccountId = account("validator", 0, 0);
+		end_round_with_points::<T>(&validator);
+	}: { Pallet::<T>::note_participation(&validator, 1); }
+	verify {
+		assert_eq!(UnclaimedRewards::<T>::get(&validator), T::Currency::minimum_balance());
+		assert_eq!(
+			ValidatorPoints::<T>::get(&validator),
+			Some(Participation { round: CurrentRound::<T>::get(), points: 1, offline: false })
+		);
+	}
+
+	// Worst case: The round has rewards to share.
+	on_session_end {
+		let validator: T::AccountId = account("validator", 0, 0);
+		Pallet::<T>::note_participation(&validator, 1);
+		let amount = T::Currency::minimum_balance();
+		SessionValidatorRewards::<T>::put(amount);
+		let round = CurrentRound::<T>::get();
+		let points = RoundPoints::<T>::get();
+	}: { Pallet::<T>::on_session_end(0); }
+	verify {
+		assert_eq!(RoundRewards::<T>::get(round), Some((amount, points)));
+		assert_last_event::<T>(Event::<T>::ValidatorRewardsSettled(0, amount).into());
+	}
+
+	// Worst case: Caller has `max_locks` which are all released early.
+	early_unlock {
+		let caller: T::AccountId = whitelisted_caller();
+		let max_locks = T::GenerateRewardLocks::max_locks(T::LockParametersBounds::get());
+		let mut locks: BTreeMap<T::BlockNumber, BalanceOf<T>> = BTreeMap::new();
+		let lock = T::Currency::minimum_balance();
+		let now = frame_system::Pallet::<T>::block_number();
+		for i in 0..max_locks {
+			locks.insert(now + (i + 1).into(), lock);
+		}
+		let amount = lock.saturating_mul(max_locks.into());
+		T::Currency::make_free_balance_be(&caller, amount.saturating_mul(2u32.into()));
+		RewardLocks::<T>::insert(&caller, locks);
+	}: _(RawOrigin::Signed(caller.clone()), amount)
+	verify {
+		assert_eq!(RewardLocks::<T>::get(&caller).iter().count(), 0);
+	}
+
+	set_account_lock_params {
+		let caller: T::AccountId = whitelisted_caller();
+		let lock_params = LockParameters {period: 150, divide: 25};
+	}: _(RawOrigin::Signed(caller.clone()), Some(lock_params))
+	verify {
+		assert_eq!(AccountLockParams::<T>::get(&caller), Some(lock_params));
+	}
+
+	set_emission_curve {
+		let curve = EmissionCurve {
+			initial_reward: T::Currency::minimum_balance(),
+			era_length: 100u32.into(),
+			offset: Zero::zero(),
+			decay: FixedU128::from_rational(5, 6),
+			rounding: Zero::zero(),
+		};
+	}: _(RawOrigin::Root, Some(curve.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::EmissionCurveChanged(Some(curve)).into());
+	}
+
+	set_reward_split {
+		let split = RewardShares {
+			miner: Perbill::from_percent(50),
+			validators: Perbill::from_percent(30),
+			treasury: Perbill::from_percent(20),
+		};
+	}: _(RawOrigin::Root, Some(split))
+	verify {
+		assert_last_event::<T>(Event::<T>::RewardSplitChanged(split).into());
+	}
 }
 
 #[cfg(test)]
@@ -127,8 +251,16 @@ mod tests {
 			assert_ok!(test_benchmark_on_finalize::<Test>());
 			assert_ok!(test_benchmark_on_initialize::<Test>());
 			assert_ok!(test_benchmark_unlock::<Test>());
+			assert_ok!(test_benchmark_lock::<Test>());
 			assert_ok!(test_benchmark_set_schedule::<Test>());
 			assert_ok!(test_benchmark_set_lock_params::<Test>());
+			assert_ok!(test_benchmark_set_reward_split::<Test>());
+			assert_ok!(test_benchmark_claim_rewards::<Test>());
+			assert_ok!(test_benchmark_note_participation::<Test>());
+			assert_ok!(test_benchmark_on_session_end::<Test>());
+			assert_ok!(test_benchmark_early_unlock::<Test>());
+			assert_ok!(test_benchmark_set_account_lock_params::<Test>());
+			assert_ok!(test_benchmark_set_emission_curve::<Test>());
 		});
 	}
 }
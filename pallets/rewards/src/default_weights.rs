@@ -31,8 +31,8 @@ impl crate::WeightInfo for () {
 	}
 	fn on_finalize() -> Weight {
 		(121_500_000 as Weight)
-			.saturating_add(DbWeight::get().reads(5 as Weight))
-			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
 	}
 	fn unlock() -> Weight {
 		(46_000_000 as Weight)
@@ -50,4 +50,33 @@ impl crate::WeightInfo for () {
 	fn set_lock_params() -> Weight {
 		(0 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_reward_split() -> Weight {
+		(12_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn note_participation() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn on_session_end() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn early_unlock() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_account_lock_params() -> Weight {
+		(14_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_emission_curve() -> Weight {
+		(16_000_000 as Weight).saturating_add(DbWeight::get().writes(2 as Weight))
+	}
 }
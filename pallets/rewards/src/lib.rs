@@ -24,7 +24,7 @@
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 mod default_weights;
-mod migrations;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -32,26 +32,34 @@ mod tests;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	decl_error, decl_event, decl_module, decl_storage, ensure,
-	traits::{Currency, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	ensure,
+	traits::{
+		Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, OnUnbalanced,
+		StorageVersion, WithdrawReasons,
+	},
 	weights::Weight,
 };
-use frame_system::{ensure_root, ensure_signed};
 use sp_consensus_poscan::POSCAN_ENGINE_ID;
-use sp_runtime::traits::{Saturating, Zero};
-use sp_runtime::Perbill;
+use sp_runtime::traits::{AtLeast32BitUnsigned, One, Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::{FixedPointNumber, FixedPointOperand, FixedU128, Perbill};
 use sp_std::{
 	collections::btree_map::BTreeMap, iter::FromIterator, ops::Bound::Included, prelude::*,
 };
 use sp_std::convert::TryInto;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 use log;
 use rewards_api::RewardLocksApi;
-use validator_set_api::ValidatorSetApi;
+use validator_set_api::{ValidatorParticipation, ValidatorSetApi};
 pub const LOG_TARGET: &'static str = "runtime::validator-set";
 
+pub use pallet::*;
 
+/// The current storage version.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub struct LockBounds {
 	pub period_max: u16,
 	pub period_min: u16,
@@ -65,6 +73,187 @@ pub struct LockParameters {
 	pub divide: u16,
 }
 
+/// Penalty of `early_unlock`, growing linearly from `min` for locks about to expire to `max`
+/// for locks expiring `period` blocks or more from now.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PenaltyCurve<BlockNumber> {
+	pub min: Perbill,
+	pub max: Perbill,
+	pub period: BlockNumber,
+}
+
+/// Participation points of a validator in a round of validator rewards. A round lasts a
+/// session, and the points of an ended round are settled by `Pallet::settle_validator`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct Participation {
+	/// Round the points were earned in.
+	pub round: u32,
+	/// Points earned in the round.
+	pub points: u32,
+	/// Whether the validator was reported offline in the round, forfeiting its points.
+	pub offline: bool,
+}
+
+/// Shares of a block reward going to its miner, to the validators and to the treasury
+/// (`Config::DonationDestination`). They add up to 100%.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RewardShares {
+	/// Share of the block author.
+	pub miner: Perbill,
+	/// Share of the validators, shared by participation points.
+	pub validators: Perbill,
+	/// Share of the treasury.
+	pub treasury: Perbill,
+}
+
+impl RewardShares {
+	/// Whether the shares add up to 100%.
+	pub fn is_valid(&self) -> bool {
+		self.miner.deconstruct() as u64 +
+			self.validators.deconstruct() as u64 +
+			self.treasury.deconstruct() as u64 ==
+			Perbill::one().deconstruct() as u64
+	}
+}
+
+/// Block reward decaying by `decay` every era of `era_length` blocks, rounded to the nearest
+/// multiple of `rounding`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EmissionCurve<BlockNumber, Balance> {
+	/// Reward of the first era.
+	pub initial_reward: Balance,
+	/// Length of an era in blocks.
+	pub era_length: BlockNumber,
+	/// Blocks of the first era before genesis.
+	pub offset: BlockNumber,
+	/// Reward of an era relative to the previous one.
+	pub decay: FixedU128,
+	/// Rewards are rounded to multiples of this amount, none if zero.
+	pub rounding: Balance,
+}
+
+impl<BlockNumber, Balance> EmissionCurve<BlockNumber, Balance> where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+	Balance: AtLeast32BitUnsigned + FixedPointOperand + Copy,
+{
+	/// Curve with a constant `reward`.
+	pub fn constant(reward: Balance) -> Self {
+		Self {
+			initial_reward: reward,
+			era_length: One::one(),
+			offset: Zero::zero(),
+			decay: FixedU128::one(),
+			rounding: Zero::zero(),
+		}
+	}
+
+	/// Whether eras are not empty and rewards do not grow.
+	pub fn is_valid(&self) -> bool {
+		!self.era_length.is_zero() && self.decay <= FixedU128::one()
+	}
+
+	/// Era of block `n`.
+	pub fn era(&self, n: BlockNumber) -> u32 {
+		if self.era_length.is_zero() {
+			return 0
+		}
+		(n.saturating_add(self.offset) / self.era_length).unique_saturated_into()
+	}
+
+	/// Reward of block `n`.
+	pub fn reward_at(&self, n: BlockNumber) -> Balance {
+		let reward = self.decay
+			.saturating_pow(self.era(n) as usize)
+			.saturating_mul_int(self.initial_reward);
+		if self.rounding.is_zero() {
+			return reward
+		}
+		let half = self.rounding / 2u32.into();
+		reward.saturating_add(half) / self.rounding * self.rounding
+	}
+}
+
+/// Projected emission state at a block, see `Pallet::project_emission`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct EmissionPoint<BlockNumber, Balance> {
+	/// Block number.
+	pub block: BlockNumber,
+	/// Block reward at this block.
+	pub reward: Balance,
+	/// Total supply including the rewards issued so far.
+	pub total_supply: Balance,
+	/// Rewards issued to miners so far.
+	pub miner_issued: Balance,
+	/// Rewards issued to validators so far.
+	pub validators_issued: Balance,
+	/// Rewards issued to the treasury so far.
+	pub treasury_issued: Balance,
+	/// Part of the issued rewards still locked at this block.
+	pub locked: Balance,
+	/// Part of the issued rewards already unlocked at this block.
+	pub liquid: Balance,
+}
+
+/// Reason an emission projection is refused, see `Pallet::check_projection`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub enum ProjectionError {
+	/// `to` is lower than `from`.
+	EmptyRange,
+	/// `to - from` is longer than the projection limit.
+	RangeTooLong,
+	/// Lock parameters are out of `LockParametersBounds` or the period is not divisible.
+	InvalidLockParams,
+}
+
+/// Reward locks of an account at a block, see `Pallet::lock_schedule`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct LockSchedule<BlockNumber, Balance> {
+	/// Locks still in force, by the block they expire at.
+	pub unlocks: Vec<(BlockNumber, Balance)>,
+	/// Total of the locks still in force.
+	pub locked: Balance,
+	/// Expired locks, freed by `unlock`.
+	pub unlockable: Balance,
+	/// Lock parameters new rewards are locked with, `None` for the runtime defaults.
+	pub lock_params: Option<LockParameters>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to project the emission of block rewards.
+	#[api_version(2)]
+	pub trait RewardsApi<BlockNumber, Balance> where
+		BlockNumber: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Project the emission over `from..=to` sampled every `step` blocks, starting from the
+		/// current total issuance. `lock_params` are taken as chosen by accounts, earning the
+		/// lock bonus, and the current lock parameters are used with no bonus if it is `None`.
+		/// The range is bounded by the runtime and `lock_params` must pass `check_lock_params`.
+		fn emission_projection(
+			from: BlockNumber,
+			to: BlockNumber,
+			step: BlockNumber,
+			validators: u32,
+			lock_params: Option<LockParameters>,
+		) -> Result<Vec<EmissionPoint<BlockNumber, Balance>>, ProjectionError>;
+
+		/// Emission curve set by governance, `None` if the reward only follows `set_schedule`.
+		fn emission_curve() -> Option<EmissionCurve<BlockNumber, Balance>>;
+	}
+
+	/// API to query the reward locks of accounts.
+	pub trait LockScheduleApi<AccountId, BlockNumber, Balance> where
+		AccountId: codec::Codec,
+		BlockNumber: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Reward locks of `account` at the current block.
+		fn lock_schedule(account: AccountId) -> LockSchedule<BlockNumber, Balance>;
+	}
+}
+
 /// Trait for generating reward locks.
 pub trait GenerateRewardLocks<T: Config> {
 	/// Generate reward locks.
@@ -75,8 +264,6 @@ pub trait GenerateRewardLocks<T: Config> {
 	) -> BTreeMap<T::BlockNumber, BalanceOf<T>>;
 
 	fn max_locks(lock_bounds: LockBounds) -> u32;
-
-	fn calc_rewards(when: T::BlockNumber) -> BalanceOf<T>;
 }
 
 // impl<T: Config> GenerateRewardLocks<T> for () {
@@ -91,10 +278,6 @@ pub trait GenerateRewardLocks<T: Config> {
 // 	fn max_locks(_lock_bounds: LockBounds) -> u32 {
 // 		0
 // 	}
-//
-// 	fn calc_rewards(_when: T::BlockNumber) -> BalanceOf<T>{
-// 		0u32.into()
-// 	}
 // }
 
 pub trait WeightInfo {
@@ -104,34 +287,238 @@ pub trait WeightInfo {
 	fn lock() -> Weight;
 	fn set_schedule() -> Weight;
 	fn set_lock_params() -> Weight;
-}
-
-/// Config for rewards.
-pub trait Config: frame_system::Config {
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-	/// An implementation of on-chain currency.
-	type Currency: LockableCurrency<Self::AccountId>;
-	/// Donation destination.
-	type DonationDestination: Get<Self::AccountId>;
-	/// Generate reward locks.
-	type GenerateRewardLocks: GenerateRewardLocks<Self>;
-	/// Weights for this pallet.
-	type WeightInfo: WeightInfo;
-	/// Lock Parameters Bounds.
-	type LockParametersBounds: Get<LockBounds>;
-	/// Pallet validator
-	type ValidatorSet: ValidatorSetApi<Self::AccountId>;
-	// Percent of rewars for miner
-	type MinerRewardsPercent: Get<u32>;
+	fn set_reward_split() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn note_participation() -> Weight;
+	fn on_session_end() -> Weight;
+	fn early_unlock() -> Weight;
+	fn set_account_lock_params() -> Weight;
+	fn set_emission_curve() -> Weight;
 }
 
 /// Type alias for currency balance.
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// Config for rewards.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// An implementation of on-chain currency.
+		type Currency: LockableCurrency<Self::AccountId>;
+		/// Donation destination.
+		#[pallet::constant]
+		type DonationDestination: Get<Self::AccountId>;
+		/// Generate reward locks.
+		type GenerateRewardLocks: GenerateRewardLocks<Self>;
+		/// Weights for this pallet.
+		type WeightInfo: WeightInfo;
+		/// Lock Parameters Bounds.
+		#[pallet::constant]
+		type LockParametersBounds: Get<LockBounds>;
+		/// Pallet validator
+		type ValidatorSet: ValidatorSetApi<Self::AccountId>;
+		// Percent of rewars for miner
+		#[pallet::constant]
+		type MinerRewardsPercent: Get<u32>;
+		/// Percent of rewards for the treasury, unless set by `set_reward_split`.
+		#[pallet::constant]
+		type TreasuryRewardsPercent: Get<u32>;
+		/// Penalty of unlocking rewards early.
+		#[pallet::constant]
+		type EarlyUnlockPenalty: Get<PenaltyCurve<Self::BlockNumber>>;
+		/// Handler of early unlock penalties, e.g. the treasury. `()` burns them.
+		type EarlyUnlockPenaltyHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Bonus on the rewards of accounts choosing the longest lock period, see
+		/// `Pallet::lock_bonus`.
+		#[pallet::constant]
+		type MaxLockBonus: Get<Perbill>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Current block author.
+	#[pallet::storage]
+	#[pallet::getter(fn author)]
+	pub type Author<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// Current block reward for miner.
+	#[pallet::storage]
+	#[pallet::getter(fn reward)]
+	pub type Reward<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Pending reward locks.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_locks)]
+	pub type RewardLocks<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BTreeMap<T::BlockNumber, BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Reward changes planned in the future.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_changes)]
+	pub type RewardChanges<T: Config> =
+		StorageValue<_, BTreeMap<T::BlockNumber, BalanceOf<T>>, ValueQuery>;
+
+	/// Current block mints.
+	#[pallet::storage]
+	#[pallet::getter(fn mints)]
+	pub type Mints<T: Config> = StorageValue<_, BTreeMap<T::AccountId, BalanceOf<T>>, ValueQuery>;
+
+	/// Mint changes planned in the future.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_changes)]
+	pub type MintChanges<T: Config> = StorageValue<
+		_,
+		BTreeMap<T::BlockNumber, BTreeMap<T::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// Lock parameters (period and divide).
+	#[pallet::storage]
+	#[pallet::getter(fn lock_params)]
+	pub type LockParams<T> = StorageValue<_, LockParameters>;
+
+	/// Emission curve the reward follows, see `Pallet::set_emission_curve`.
+	#[pallet::storage]
+	#[pallet::getter(fn emission_curve)]
+	pub type RewardCurve<T: Config> =
+		StorageValue<_, EmissionCurve<T::BlockNumber, BalanceOf<T>>>;
+
+	/// Lock parameters chosen by accounts, in place of `LockParams`.
+	#[pallet::storage]
+	#[pallet::getter(fn account_lock_params)]
+	pub type AccountLockParams<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, LockParameters>;
+
+	/// Reward shares set by governance, see `Pallet::reward_split`.
+	#[pallet::storage]
+	pub type RewardSplit<T> = StorageValue<_, RewardShares>;
+
+	/// Validator rewards of the current round, shared by participation points at its end.
+	#[pallet::storage]
+	#[pallet::getter(fn session_validator_rewards)]
+	pub type SessionValidatorRewards<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Round of validator rewards in progress. A round ends with each session.
+	#[pallet::storage]
+	#[pallet::getter(fn current_round)]
+	pub type CurrentRound<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Participation points noted in the current round.
+	#[pallet::storage]
+	#[pallet::getter(fn round_points)]
+	pub type RoundPoints<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Validator rewards of ended rounds left to settle, and the points they are shared by.
+	#[pallet::storage]
+	#[pallet::getter(fn round_rewards)]
+	pub type RoundRewards<T: Config> = StorageMap<_, Twox64Concat, u32, (BalanceOf<T>, u32)>;
+
+	/// Latest participation of validators. Points of an ended round are settled when the
+	/// validator participates again or claims its rewards.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_points)]
+	pub type ValidatorPoints<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Participation>;
+
+	/// Settled validator rewards, paid out by `claim_rewards`.
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_rewards)]
+	pub type UnclaimedRewards<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial block reward.
+		pub reward: BalanceOf<T>,
+		/// Initial block mints.
+		pub mints: BTreeMap<T::AccountId, BalanceOf<T>>,
+		/// Emission curve, setting the initial block reward in place of `reward`.
+		pub emission_curve: Option<EmissionCurve<T::BlockNumber, BalanceOf<T>>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				reward: Default::default(),
+				mints: Default::default(),
+				emission_curve: Default::default(),
+			}
+		}
+	}
 
-decl_error! {
-	pub enum Error for Module<T: Config> {
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			Reward::<T>::put(self.reward);
+			Mints::<T>::put(&self.mints);
+			if let Some(curve) = &self.emission_curve {
+				assert!(curve.is_valid(), "Invalid emission curve");
+				Reward::<T>::put(curve.reward_at(Zero::zero()));
+				RewardCurve::<T>::put(curve);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new schedule has been set.
+		ScheduleSet,
+		/// Reward has been sent.
+		Rewarded(T::AccountId, BalanceOf<T>),
+		/// Reward has been changed.
+		RewardChanged(BalanceOf<T>),
+		/// Mint has been sent.
+		Minted(T::AccountId, BalanceOf<T>),
+		/// Mint has been changed.
+		MintsChanged(BTreeMap<T::AccountId, BalanceOf<T>>),
+		/// Lock Parameters have been changed.
+		LockParamsChanged(LockParameters),
+		/// Lock set.
+		Locked(T::AccountId, BalanceOf<T>),
+		/// Reward shares have been changed.
+		RewardSplitChanged(RewardShares),
+		/// Treasury share of the block reward has been sent.
+		TreasuryRewarded(BalanceOf<T>),
+		/// Validator rewards of a session have been made claimable.
+		ValidatorRewardsSettled(u32, BalanceOf<T>),
+		/// Validator rewards have been claimed.
+		RewardsClaimed(T::AccountId, BalanceOf<T>),
+		/// Validator share with no validator to pay has been sent to the treasury.
+		ValidatorShareRedirected(T::AccountId, BalanceOf<T>),
+		/// Locked rewards have been unlocked early, with a penalty.
+		EarlyUnlocked(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Lock parameters of an account have been changed.
+		AccountLockParamsChanged(T::AccountId, Option<LockParameters>),
+		/// Bonus for a longer lock has been added to a reward.
+		LockBonusRewarded(T::AccountId, BalanceOf<T>),
+		/// Emission curve has been changed.
+		EmissionCurveChanged(Option<EmissionCurve<T::BlockNumber, BalanceOf<T>>>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
 		/// Reward set is too low.
 		RewardTooLow,
 		/// Mint value is too low.
@@ -142,39 +529,20 @@ decl_error! {
 		LockParamsOutOfBounds,
 		/// Lock period is not a mutiple of the divide.
 		LockPeriodNotDivisible,
+		/// Reward shares do not add up to 100%.
+		InvalidRewardSplit,
+		/// There are no rewards to claim.
+		NothingToClaim,
+		/// Not enough rewards are locked.
+		InsufficientLocked,
+		/// Emission curve has empty eras or a growing reward.
+		InvalidEmissionCurve,
+		/// The amount to unlock is zero.
+		ZeroUnlock,
 	}
-}
-
-decl_storage! {
-	trait Store for Module<T: Config> as Rewards {
-		/// Current block author.
-		Author get(fn author): Option<T::AccountId>;
-
-		/// Current block reward for miner.
-		Reward get(fn reward) config(): BalanceOf<T>;
-		/// Pending reward locks.
-		RewardLocks get(fn reward_locks): map hasher(twox_64_concat) T::AccountId => BTreeMap<T::BlockNumber, BalanceOf<T>>;
-		/// Reward changes planned in the future.
-		RewardChanges get(fn reward_changes): BTreeMap<T::BlockNumber, BalanceOf<T>>;
-
-		/// Current block mints.
-		Mints get(fn mints) config(): BTreeMap<T::AccountId, BalanceOf<T>>;
-		/// Mint changes planned in the future.
-		MintChanges get(fn mint_changes): BTreeMap<T::BlockNumber, BTreeMap<T::AccountId, BalanceOf<T>>>;
-
-		/// Lock parameters (period and divide).
-		LockParams get(fn lock_params): Option<LockParameters>;
-
-		StorageVersion build(|_| migrations::StorageVersion::V1): migrations::StorageVersion;
-	}
-}
-
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
-
-		fn deposit_event() = default;
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			let author = frame_system::Pallet::<T>::digest()
 				.logs
@@ -188,16 +556,21 @@ decl_module! {
 				.next();
 
 			if let Some(author) = author {
-				<Self as Store>::Author::put(author);
+				Author::<T>::put(author);
 			}
 
-			let cur_block_number = <frame_system::Pallet<T>>::block_number();
-			let cur_reward = T::GenerateRewardLocks::calc_rewards(cur_block_number);
-			let d = u128::from_le_bytes(cur_reward.encode().try_into().unwrap());
-
-			log::debug!(target: LOG_TARGET, "cur_reward: {}", d);
+			// The curve sets the reward when an era starts, and reward changes scheduled by
+			// `set_schedule` hold until the next one.
+			if let Some(curve) = RewardCurve::<T>::get() {
+				if now.is_zero() || curve.era(now) != curve.era(now - One::one()) {
+					let cur_reward = curve.reward_at(now);
+					let d = u128::from_le_bytes(cur_reward.encode().try_into().unwrap());
+					log::debug!(target: LOG_TARGET, "cur_reward: {}", d);
 
-			Reward::<T>::set(cur_reward);
+					Reward::<T>::set(cur_reward);
+					Self::deposit_event(Event::<T>::RewardChanged(cur_reward));
+				}
+			}
 
 			RewardChanges::<T>::mutate(|reward_changes| {
 				let mut removing = Vec::new();
@@ -233,7 +606,7 @@ decl_module! {
 		}
 
 		fn on_finalize(now: T::BlockNumber) {
-			if let Some(author) = <Self as Store>::Author::get() {
+			if let Some(author) = Author::<T>::get() {
 				let reward = Reward::<T>::get();
 				Self::do_reward(&author, reward, now);
 			}
@@ -241,25 +614,33 @@ decl_module! {
 			let mints = Mints::<T>::get();
 			Self::do_mints(&mints);
 
-			<Self as Store>::Author::kill();
+			Author::<T>::kill();
 		}
 
-		fn on_runtime_upgrade() -> frame_support::weights::Weight {
-			let version = StorageVersion::get();
-			let new_version = version.migrate::<T>();
-			StorageVersion::put(new_version);
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
 
-			0
+		fn integrity_test() {
+			assert!(
+				T::MinerRewardsPercent::get() + T::TreasuryRewardsPercent::get() <= 100,
+				"Miner and treasury rewards exceed 100%"
+			);
+			let curve = T::EarlyUnlockPenalty::get();
+			assert!(curve.min <= curve.max, "Minimal early unlock penalty exceeds the maximal one");
 		}
+	}
 
-		#[weight = T::WeightInfo::set_schedule()]
-		fn set_schedule(
-			origin,
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::set_schedule())]
+		pub fn set_schedule(
+			origin: OriginFor<T>,
 			reward: BalanceOf<T>,
 			mints: Vec<(T::AccountId, BalanceOf<T>)>,
 			reward_changes: Vec<(T::BlockNumber, BalanceOf<T>)>,
 			mint_changes: Vec<(T::BlockNumber, Vec<(T::AccountId, BalanceOf<T>)>)>,
-		) {
+		) -> DispatchResult {
 			ensure_root(origin)?;
 
 			let mints = BTreeMap::from_iter(mints.into_iter());
@@ -282,42 +663,44 @@ decl_module! {
 			}
 
 			Reward::<T>::put(reward);
-			Self::deposit_event(RawEvent::RewardChanged(reward));
+			Self::deposit_event(Event::RewardChanged(reward));
 
 			Mints::<T>::put(mints.clone());
-			Self::deposit_event(RawEvent::MintsChanged(mints));
+			Self::deposit_event(Event::MintsChanged(mints));
 
 			RewardChanges::<T>::put(reward_changes);
 			MintChanges::<T>::put(mint_changes);
-			Self::deposit_event(RawEvent::ScheduleSet);
+			Self::deposit_event(Event::ScheduleSet);
+
+			Ok(())
 		}
 
-		#[weight = T::WeightInfo::set_lock_params()]
-		fn set_lock_params(origin, lock_params: LockParameters) {
+		#[pallet::weight(T::WeightInfo::set_lock_params())]
+		pub fn set_lock_params(origin: OriginFor<T>, lock_params: LockParameters) -> DispatchResult {
 			ensure_root(origin)?;
+			Self::check_lock_params(&lock_params)?;
 
-			let bounds = T::LockParametersBounds::get();
-			ensure!((bounds.period_min..=bounds.period_max).contains(&lock_params.period) &&
-				(bounds.divide_min..=bounds.divide_max).contains(&lock_params.divide), Error::<T>::LockParamsOutOfBounds);
-			ensure!(lock_params.period % lock_params.divide == 0, Error::<T>::LockPeriodNotDivisible);
+			LockParams::<T>::put(lock_params);
+			Self::deposit_event(Event::LockParamsChanged(lock_params));
 
-			LockParams::put(lock_params);
-			Self::deposit_event(RawEvent::LockParamsChanged(lock_params));
+			Ok(())
 		}
 
 		/// Unlock any vested rewards for `target` account.
-		#[weight = T::WeightInfo::unlock()]
-		fn unlock(origin) {
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(origin: OriginFor<T>) -> DispatchResult {
 			let target = ensure_signed(origin)?;
 
 			let locks = Self::reward_locks(&target);
 			let current_number = frame_system::Pallet::<T>::block_number();
 			Self::do_update_reward_locks(&target, locks, current_number);
+
+			Ok(())
 		}
 
 		/// Unlock any vested rewards for `target` account.
-		#[weight = T::WeightInfo::lock()]
-		fn lock(origin, amount: BalanceOf<T>, when: T::BlockNumber)  {
+		#[pallet::weight(T::WeightInfo::lock())]
+		pub fn lock(origin: OriginFor<T>, amount: BalanceOf<T>, when: T::BlockNumber) -> DispatchResult {
 			let target = ensure_signed(origin)?;
 
 			let current_number = frame_system::Pallet::<T>::block_number();
@@ -332,58 +715,376 @@ decl_module! {
 				locks.insert(when, new_balance);
 
 				Self::do_update_reward_locks(&target, locks, current_number);
-				Self::deposit_event(RawEvent::Locked(target, amount));
-            }
+				Self::deposit_event(Event::Locked(target, amount));
+			}
+
+			Ok(())
 		}
-	}
-}
 
-decl_event! {
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId, Balance = BalanceOf<T> {
-		/// A new schedule has been set.
-		ScheduleSet,
-		/// Reward has been sent.
-		Rewarded(AccountId, Balance),
-		/// Reward has been changed.
-		RewardChanged(Balance),
-		/// Mint has been sent.
-		Minted(AccountId, Balance),
-		/// Mint has been changed.
-		MintsChanged(BTreeMap<AccountId, Balance>),
-		/// Lock Parameters have been changed.
-		LockParamsChanged(LockParameters),
-		/// Lock set.
-		Locked(AccountId, Balance),
+		/// Set the shares of the block reward going to the miner, the validators and the
+		/// treasury. `None` restores `MinerRewardsPercent` and `TreasuryRewardsPercent`.
+		#[pallet::weight(T::WeightInfo::set_reward_split())]
+		pub fn set_reward_split(origin: OriginFor<T>, split: Option<RewardShares>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(split.map_or(true, |split| split.is_valid()), Error::<T>::InvalidRewardSplit);
+
+			RewardSplit::<T>::set(split);
+			Self::deposit_event(Event::RewardSplitChanged(Self::reward_split()));
+
+			Ok(())
+		}
+
+		/// Pay out the caller's validator rewards of ended sessions. They are locked as block
+		/// rewards, starting at the current block.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::settle_validator(&who);
+			let amount = UnclaimedRewards::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			let current_number = frame_system::Pallet::<T>::block_number();
+			Self::do_reward_per_account(&who, amount, current_number);
+			Self::deposit_event(Event::RewardsClaimed(who, amount));
+
+			Ok(())
+		}
+
+		/// Release `amount` of the caller's locked rewards before they expire, nearest locks
+		/// first. A penalty set by `EarlyUnlockPenalty` for the time each lock has left is
+		/// withdrawn from the released funds.
+		#[pallet::weight(T::WeightInfo::early_unlock())]
+		pub fn early_unlock(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroUnlock);
+
+			let current_number = frame_system::Pallet::<T>::block_number();
+			let mut locks = Self::reward_locks(&who);
+			let mut remaining = amount;
+			let mut penalty: BalanceOf<T> = Zero::zero();
+
+			for (when, lock) in locks.iter_mut() {
+				if remaining.is_zero() {
+					break
+				}
+				// Expired locks are released by `do_update_reward_locks` anyway.
+				if *when <= current_number {
+					continue
+				}
+				let released = sp_std::cmp::min(*lock, remaining);
+				let rate = Self::early_unlock_penalty(*when - current_number);
+				penalty = penalty.saturating_add(rate * released);
+				*lock = lock.saturating_sub(released);
+				remaining = remaining.saturating_sub(released);
+			}
+			ensure!(remaining.is_zero(), Error::<T>::InsufficientLocked);
+
+			locks.retain(|_, lock| !lock.is_zero());
+			Self::do_update_reward_locks(&who, locks, current_number);
+
+			if !penalty.is_zero() {
+				let imbalance = T::Currency::withdraw(
+					&who,
+					penalty,
+					WithdrawReasons::TRANSFER,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::EarlyUnlockPenaltyHandler::on_unbalanced(imbalance);
+			}
+			Self::deposit_event(Event::EarlyUnlocked(who, amount, penalty));
+
+			Ok(())
+		}
+
+		/// Choose the lock parameters of the caller's rewards, within `LockParametersBounds`.
+		/// Longer lock periods earn a bonus, see `Pallet::lock_bonus`. `None` restores
+		/// `LockParams`.
+		#[pallet::weight(T::WeightInfo::set_account_lock_params())]
+		pub fn set_account_lock_params(
+			origin: OriginFor<T>,
+			lock_params: Option<LockParameters>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if let Some(lock_params) = &lock_params {
+				Self::check_lock_params(lock_params)?;
+			}
+
+			AccountLockParams::<T>::set(&who, lock_params);
+			Self::deposit_event(Event::AccountLockParamsChanged(who, lock_params));
+
+			Ok(())
+		}
+
+		/// Set the emission curve the block reward follows from now on. With `None` the reward
+		/// only changes through `set_schedule`.
+		#[pallet::weight(T::WeightInfo::set_emission_curve())]
+		pub fn set_emission_curve(
+			origin: OriginFor<T>,
+			curve: Option<EmissionCurve<T::BlockNumber, BalanceOf<T>>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if let Some(curve) = &curve {
+				ensure!(curve.is_valid(), Error::<T>::InvalidEmissionCurve);
+
+				let reward = curve.reward_at(frame_system::Pallet::<T>::block_number());
+				Reward::<T>::put(reward);
+				Self::deposit_event(Event::RewardChanged(reward));
+			}
+
+			RewardCurve::<T>::set(curve.clone());
+			Self::deposit_event(Event::EmissionCurveChanged(curve));
+
+			Ok(())
+		}
 	}
 }
+
 // Must be the same as in validator-set pallet
-const REWARDS_ID: LockIdentifier = *b"rewards ";
+pub const REWARDS_ID: LockIdentifier = *b"rewards ";
+
+impl<T: Config> Pallet<T> {
+	/// Check lock parameters against `LockParametersBounds`.
+	pub fn check_lock_params(lock_params: &LockParameters) -> Result<(), Error<T>> {
+		let bounds = T::LockParametersBounds::get();
+		ensure!((bounds.period_min..=bounds.period_max).contains(&lock_params.period) &&
+			(bounds.divide_min..=bounds.divide_max).contains(&lock_params.divide), Error::<T>::LockParamsOutOfBounds);
+		ensure!(lock_params.period % lock_params.divide == 0, Error::<T>::LockPeriodNotDivisible);
+		Ok(())
+	}
 
-impl<T: Config> Module<T> {
-	fn do_reward(author: &T::AccountId, reward: BalanceOf<T>, when: T::BlockNumber) {
-		let miner_total = Perbill::from_percent(T::MinerRewardsPercent::get()) * reward;
-		let validator_total = reward - miner_total;
+	/// Check a projection over `from..=to` of at most `max_blocks` blocks with `lock_params`.
+	pub fn check_projection(
+		from: T::BlockNumber,
+		to: T::BlockNumber,
+		lock_params: Option<&LockParameters>,
+		max_blocks: T::BlockNumber,
+	) -> Result<(), ProjectionError> {
+		ensure!(from <= to, ProjectionError::EmptyRange);
+		ensure!(to - from <= max_blocks, ProjectionError::RangeTooLong);
+		if let Some(lock_params) = lock_params {
+			Self::check_lock_params(lock_params).map_err(|_| ProjectionError::InvalidLockParams)?;
+		}
+		Ok(())
+	}
 
-		let d = u128::from_le_bytes(miner_total.encode().try_into().unwrap());
-		log::debug!(target: LOG_TARGET, "miner_reword: {}", d);
+	/// Lock parameters rewards of `account` are locked with.
+	pub fn effective_lock_params(account: &T::AccountId) -> Option<LockParameters> {
+		Self::account_lock_params(account).or_else(Self::lock_params)
+	}
+
+	/// Bonus rate on rewards locked with `lock_params` chosen by an account, growing linearly
+	/// from nothing at `period_min` to `MaxLockBonus` at `period_max`.
+	pub fn lock_bonus(lock_params: &LockParameters) -> Perbill {
+		let bounds = T::LockParametersBounds::get();
+		let range = bounds.period_max.saturating_sub(bounds.period_min);
+		if range == 0 {
+			return Perbill::zero()
+		}
+		let period = lock_params.period.saturating_sub(bounds.period_min).min(range);
+		Perbill::from_rational(u32::from(period), u32::from(range)) * T::MaxLockBonus::get()
+	}
+
+	/// Penalty rate of unlocking a lock with `remaining` blocks left.
+	pub fn early_unlock_penalty(remaining: T::BlockNumber) -> Perbill {
+		let curve = T::EarlyUnlockPenalty::get();
+		if remaining >= curve.period {
+			return curve.max
+		}
+		let progress = Perbill::from_rational(remaining, curve.period);
+		curve.min.saturating_add(progress * curve.max.saturating_sub(curve.min))
+	}
+
+	/// Reward shares in effect.
+	pub fn reward_split() -> RewardShares {
+		RewardSplit::<T>::get().unwrap_or_else(Self::default_reward_split)
+	}
+
+	/// Reward shares set by `MinerRewardsPercent` and `TreasuryRewardsPercent`.
+	pub fn default_reward_split() -> RewardShares {
+		let miner = Perbill::from_percent(T::MinerRewardsPercent::get());
+		let treasury = Perbill::from_percent(T::TreasuryRewardsPercent::get());
+		RewardShares { miner, validators: Perbill::one() - miner - treasury, treasury }
+	}
+
+	/// Reward locks of `account` at block `now`.
+	pub fn lock_schedule(
+		account: &T::AccountId,
+		now: T::BlockNumber,
+	) -> LockSchedule<T::BlockNumber, BalanceOf<T>> {
+		let mut unlocks = Vec::new();
+		let mut locked: BalanceOf<T> = Zero::zero();
+		let mut unlockable: BalanceOf<T> = Zero::zero();
+
+		// Expired locks are kept until the next `do_update_reward_locks`.
+		for (block_number, balance) in Self::reward_locks(account) {
+			if block_number <= now {
+				unlockable = unlockable.saturating_add(balance);
+			} else {
+				locked = locked.saturating_add(balance);
+				unlocks.push((block_number, balance));
+			}
+		}
+
+		LockSchedule { unlocks, locked, unlockable, lock_params: Self::effective_lock_params(account) }
+	}
+
+	/// Split a block reward into the miner part, the validators part and the treasury part.
+	/// Rounding leftovers go to the validators.
+	fn split_reward(
+		split: &RewardShares,
+		reward: BalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		let miner_total = split.miner * reward;
+		let treasury_total = split.treasury * reward;
+		let validator_total = reward.saturating_sub(miner_total).saturating_sub(treasury_total);
+
+		(miner_total, validator_total, treasury_total)
+	}
+
+	/// Project the emission of block rewards following `curve` over `from..=to`, sampled every
+	/// `step` blocks and at `to`.
+	///
+	/// Every block is assumed to reward its miner, `validators` equally participating validators,
+	/// one of them taking the rounding leftovers, and the treasury as `do_reward` does with
+	/// `split`, with locks generated from `lock_params`. Miner and validator rewards get `bonus`
+	/// on top, the `lock_bonus` of accounts choosing `lock_params`. With no validators their
+	/// share goes to the treasury.
+	/// Locks of rewards issued before `from`, scheduled reward changes and mints are not taken
+	/// into account.
+	pub fn project_emission(
+		curve: &EmissionCurve<T::BlockNumber, BalanceOf<T>>,
+		split: &RewardShares,
+		from: T::BlockNumber,
+		to: T::BlockNumber,
+		step: T::BlockNumber,
+		validators: u32,
+		lock_params: Option<LockParameters>,
+		bonus: Perbill,
+		initial_supply: BalanceOf<T>,
+	) -> Vec<EmissionPoint<T::BlockNumber, BalanceOf<T>>> {
+		let step = sp_std::cmp::max(step, One::one());
+		let mut points = Vec::new();
+		let mut pending: BTreeMap<T::BlockNumber, BalanceOf<T>> = BTreeMap::new();
+		let mut miner_issued: BalanceOf<T> = Zero::zero();
+		let mut validators_issued: BalanceOf<T> = Zero::zero();
+		let mut treasury_issued: BalanceOf<T> = Zero::zero();
+		let mut locked: BalanceOf<T> = Zero::zero();
+
+		let mut now = from;
+		while now <= to {
+			let reward = curve.reward_at(now);
+			let (miner_total, validator_total, mut treasury_total) = Self::split_reward(split, reward);
+			// The validator share is paid in full, so one validator takes the rounding leftovers.
+			let (per_val, first_val) = if validators == 0 {
+				treasury_total = treasury_total.saturating_add(validator_total);
+				(Zero::zero(), Zero::zero())
+			} else {
+				let count: BalanceOf<T> = validators.into();
+				let per_val = validator_total / count;
+				(per_val, per_val.saturating_add(validator_total % count))
+			};
+			// The bonus is minted on top of the reward, as in `do_reward_per_account`.
+			let miner_total = miner_total.saturating_add(bonus * miner_total);
+			let per_val = per_val.saturating_add(bonus * per_val);
+			let first_val = first_val.saturating_add(bonus * first_val);
+			let others = validators.saturating_sub(1);
+
+			for (amount, count) in [(miner_total, 1u32), (first_val, 1), (per_val, others)] {
+				if amount.is_zero() || count == 0 {
+					continue
+				}
+				let count: BalanceOf<T> = count.into();
+				for (when, lock) in T::GenerateRewardLocks::generate_reward_locks(now, amount, lock_params) {
+					let lock = lock.saturating_mul(count);
+					let entry = pending.entry(when).or_insert_with(Zero::zero);
+					*entry = entry.saturating_add(lock);
+					locked = locked.saturating_add(lock);
+				}
+			}
+			miner_issued = miner_issued.saturating_add(miner_total);
+			validators_issued = validators_issued
+				.saturating_add(first_val)
+				.saturating_add(per_val.saturating_mul(others.into()));
+			treasury_issued = treasury_issued.saturating_add(treasury_total);
+
+			// Locks expire at their block, as in `do_update_reward_locks`.
+			while let Some((&when, &lock)) = pending.iter().next() {
+				if when > now {
+					break
+				}
+				pending.remove(&when);
+				locked = locked.saturating_sub(lock);
+			}
 
+			if ((now - from) % step).is_zero() || now == to {
+				let issued = miner_issued
+					.saturating_add(validators_issued)
+					.saturating_add(treasury_issued);
+				points.push(EmissionPoint {
+					block: now,
+					reward,
+					total_supply: initial_supply.saturating_add(issued),
+					miner_issued,
+					validators_issued,
+					treasury_issued,
+					locked,
+					liquid: issued.saturating_sub(locked),
+				});
+			}
+
+			if now == to {
+				break
+			}
+			now += One::one();
+		}
+
+		points
+	}
+
+	fn do_reward(author: &T::AccountId, reward: BalanceOf<T>, when: T::BlockNumber) {
 		let validators = T::ValidatorSet::validators();
 
-		let n_val: usize = validators.len();
-		let per_val = Perbill::from_rational(1, n_val as u32) * validator_total;
+		let (miner_total, validator_total, treasury_total) =
+			Self::split_reward(&Self::reward_split(), reward);
+
+		let d = u128::from_le_bytes(miner_total.encode().try_into().unwrap());
+		log::debug!(target: LOG_TARGET, "miner_reword: {}", d);
 
 		Self::do_reward_per_account(author, miner_total, when);
 
-		let d = u128::from_le_bytes(per_val.encode().try_into().unwrap());
-		for val in validators.iter() {
-			log::debug!(target: LOG_TARGET, "validator_reword: {} for {:?}", d, val.encode());
-			Self::do_reward_per_account(val, per_val, when);
+		// The validator share is pooled for the session. Each validator's part of the pool,
+		// by participation, is settled lazily by `settle_validator` on `claim_rewards` or on
+		// its next participation.
+		if validators.is_empty() {
+			Self::redirect_validator_share(validator_total);
+		} else {
+			SessionValidatorRewards::<T>::mutate(|r| *r = r.saturating_add(validator_total));
+		}
+
+		// The treasury share is not locked.
+		if !treasury_total.is_zero() {
+			drop(T::Currency::deposit_creating(&T::DonationDestination::get(), treasury_total));
+			Self::deposit_event(Event::TreasuryRewarded(treasury_total));
 		}
 	}
 
 	fn do_reward_per_account(account: &T::AccountId, reward: BalanceOf<T>, when: T::BlockNumber) {
+		let mut reward = reward;
+		let lock_params = match AccountLockParams::<T>::get(account) {
+			Some(lock_params) => {
+				let bonus = Self::lock_bonus(&lock_params) * reward;
+				if !bonus.is_zero() {
+					reward = reward.saturating_add(bonus);
+					Self::deposit_event(Event::LockBonusRewarded(account.clone(), bonus));
+				}
+				Some(lock_params)
+			},
+			None => LockParams::<T>::get(),
+		};
 		let account_reward_locks =
-			T::GenerateRewardLocks::generate_reward_locks(when, reward, LockParams::get());
+			T::GenerateRewardLocks::generate_reward_locks(when, reward, lock_params);
 
 		drop(T::Currency::deposit_creating(&account, reward));
 
@@ -422,14 +1123,81 @@ impl<T: Config> Module<T> {
 			locks.remove(&block_number);
 		}
 
-		T::Currency::set_lock(
-			REWARDS_ID,
-			&author,
-			total_locked,
-			WithdrawReasons::except(WithdrawReasons::TRANSACTION_PAYMENT),
-		);
+		// Setting a lock to zero is a no-op, it has to be removed.
+		if total_locked.is_zero() {
+			T::Currency::remove_lock(REWARDS_ID, &author);
+		} else {
+			T::Currency::set_lock(
+				REWARDS_ID,
+				&author,
+				total_locked,
+				WithdrawReasons::except(WithdrawReasons::TRANSACTION_PAYMENT),
+			);
+		}
 
-		<Self as Store>::RewardLocks::insert(author, locks);
+		RewardLocks::<T>::insert(author, locks);
+	}
+
+	/// Send a validator share nobody can be paid to the treasury.
+	fn redirect_validator_share(amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return
+		}
+		let treasury = T::DonationDestination::get();
+		drop(T::Currency::deposit_creating(&treasury, amount));
+		Self::deposit_event(Event::ValidatorShareRedirected(treasury, amount));
+	}
+
+	/// End the current round, sharing its validator rewards by the points noted in it. The
+	/// shares are settled one validator at a time by `settle_validator`. With no points noted
+	/// the rewards are redirected to the treasury.
+	fn do_end_round(session: u32) {
+		let round = CurrentRound::<T>::get();
+		CurrentRound::<T>::put(round.saturating_add(1));
+		let total = SessionValidatorRewards::<T>::take();
+		let points = RoundPoints::<T>::take();
+
+		if total.is_zero() {
+			return
+		}
+		if points == 0 {
+			Self::redirect_validator_share(total);
+			return
+		}
+
+		RoundRewards::<T>::insert(round, (total, points));
+		Self::deposit_event(Event::ValidatorRewardsSettled(session, total));
+	}
+
+	/// Make the share of `who` in the rewards of the ended round it last participated in
+	/// claimable. Each validator takes its share of what the round has left, so the last one
+	/// gets the rounding leftovers and nothing is left behind.
+	fn settle_validator(who: &T::AccountId) {
+		let participation = match ValidatorPoints::<T>::get(who) {
+			Some(p) if p.round < CurrentRound::<T>::get() => p,
+			_ => return,
+		};
+		ValidatorPoints::<T>::remove(who);
+
+		let (left, points) = match RoundRewards::<T>::get(participation.round) {
+			Some(r) if participation.points > 0 => r,
+			_ => return,
+		};
+		let reward = if participation.points >= points {
+			RoundRewards::<T>::remove(participation.round);
+			left
+		} else {
+			let reward = Perbill::from_rational(participation.points, points) * left;
+			RoundRewards::<T>::insert(
+				participation.round,
+				(left.saturating_sub(reward), points - participation.points),
+			);
+			reward
+		};
+
+		let d = u128::from_le_bytes(reward.encode().try_into().unwrap());
+		log::debug!(target: LOG_TARGET, "validator_reword: {} for {:?}", d, who.encode());
+		UnclaimedRewards::<T>::mutate(who, |r| *r = r.saturating_add(reward));
 	}
 
 	fn do_mints(mints: &BTreeMap<T::AccountId, BalanceOf<T>>) {
@@ -449,3 +1217,32 @@ impl<T: Config> RewardLocksApi<T::AccountId, BalanceOf<T>> for Pallet<T> {
 			)
 	}
 }
+
+impl<T: Config> ValidatorParticipation<T::AccountId> for Pallet<T> {
+	fn note_participation(who: &T::AccountId, points: u32) {
+		Self::settle_validator(who);
+		let round = CurrentRound::<T>::get();
+		ValidatorPoints::<T>::mutate(who, |p| {
+			let p = p.get_or_insert(Participation { round, points: 0, offline: false });
+			if !p.offline {
+				p.points = p.points.saturating_add(points);
+				RoundPoints::<T>::mutate(|total| *total = total.saturating_add(points));
+			}
+		});
+	}
+
+	fn note_offline(who: &T::AccountId) {
+		Self::settle_validator(who);
+		let round = CurrentRound::<T>::get();
+		ValidatorPoints::<T>::mutate(who, |p| {
+			if let Some(p) = p {
+				RoundPoints::<T>::mutate(|total| *total = total.saturating_sub(p.points));
+			}
+			*p = Some(Participation { round, points: 0, offline: true });
+		});
+	}
+
+	fn on_session_end(index: u32) {
+		Self::do_end_round(index);
+	}
+}
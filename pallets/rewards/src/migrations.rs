@@ -17,18 +17,28 @@
 // You should have received a copy of the GNU General Public License
 // along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{BalanceOf, Config, Mints, RewardChanges};
+use crate::{
+	BalanceOf, Config, EmissionCurve, Mints, Pallet, RewardChanges, RewardCurve, STORAGE_VERSION,
+};
+#[cfg(feature = "try-runtime")]
+use crate::RewardLocks;
 use codec::{Decode, Encode};
-use frame_support::storage::StorageValue;
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+	traits::{Get, GetStorageVersion},
+	weights::Weight,
+};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{Perbill, RuntimeDebug};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use scale_info::TypeInfo;
 
-/// A value placed in storage that represents the current version of the Scheduler storage.
-/// This value is used by the `on_runtime_upgrade` logic to determine whether we run
-/// storage migration logic.
+/// Storage version kept by the pallet in its own `StorageVersion` item, before it moved to the
+/// FRAME pallet storage version. Version 1 of the latter has the layout of `V1`.
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum StorageVersion {
@@ -53,6 +63,50 @@ impl StorageVersion {
 	}
 }
 
+struct __LegacyStorageVersion;
+impl frame_support::traits::StorageInstance for __LegacyStorageVersion {
+	fn pallet_prefix() -> &'static str {
+		"Rewards"
+	}
+	const STORAGE_PREFIX: &'static str = "StorageVersion";
+}
+
+type LegacyStorageVersion = frame_support::storage::types::StorageValue<
+	__LegacyStorageVersion,
+	StorageVersion,
+	frame_support::storage::types::ValueQuery,
+>;
+
+/// Migrate the storage from the legacy version to version 1.
+pub fn migrate<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	LegacyStorageVersion::take().migrate::<T>();
+	frame_support::traits::StorageVersion::new(1).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(4, 5)
+}
+
+/// Migrate the storage to version 2, setting `curve` as the emission curve unless one is set
+/// already, e.g. to replace a reward schedule kept by the runtime.
+///
+/// It runs once, so a curve removed by governance afterwards is not put back.
+pub fn init_emission_curve<T: Config>(curve: EmissionCurve<T::BlockNumber, BalanceOf<T>>) -> Weight {
+	let weight = migrate::<T>();
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return weight.saturating_add(T::DbWeight::get().reads(1))
+	}
+
+	if !RewardCurve::<T>::exists() {
+		RewardCurve::<T>::put(curve);
+	}
+	STORAGE_VERSION.put::<Pallet<T>>();
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+}
+
 struct __CurveV0;
 impl frame_support::traits::StorageInstance for __CurveV0 {
 	fn pallet_prefix() -> &'static str {
@@ -105,3 +159,64 @@ fn migrate_v0_to_v1<T: Config>() {
 	}
 	Mints::<T>::put(mints);
 }
+
+/// Sum of the pending reward locks of all accounts.
+#[cfg(feature = "try-runtime")]
+pub fn total_reward_locks<T: Config>() -> BalanceOf<T> {
+	RewardLocks::<T>::iter_values()
+		.flat_map(|locks| locks.into_iter().map(|(_, amount)| amount))
+		.fold(Zero::zero(), |total: BalanceOf<T>, amount| total.saturating_add(amount))
+}
+
+/// Checks that the reward locks of every account are covered by its `REWARDS_ID` balance
+/// lock, as returned by `balance_lock`.
+///
+/// The balance lock may exceed the recorded locks: before released locks removed the balance
+/// lock, an account whose locks all expired kept its last lock until it got a new reward.
+#[cfg(feature = "try-runtime")]
+pub fn check_reward_locks<T: Config>(
+	balance_lock: impl Fn(&T::AccountId) -> BalanceOf<T>,
+) -> Result<(), &'static str> {
+	for (who, locks) in RewardLocks::<T>::iter() {
+		let recorded = locks
+			.values()
+			.fold(Zero::zero(), |total: BalanceOf<T>, amount| total.saturating_add(*amount));
+		if balance_lock(&who) < recorded {
+			log::error!(
+				target: crate::LOG_TARGET,
+				"Reward locks of {:?} exceed its balance lock",
+				who,
+			);
+			return Err("Reward locks are not covered by the balance lock")
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the reward locks before a runtime upgrade and returns their total, to be handed to
+/// `post_upgrade`.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>(
+	balance_lock: impl Fn(&T::AccountId) -> BalanceOf<T>,
+) -> Result<BalanceOf<T>, &'static str> {
+	check_reward_locks::<T>(balance_lock)?;
+	Ok(total_reward_locks::<T>())
+}
+
+/// Checks that the storage is migrated and the upgrade left the reward locks untouched.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>(
+	balance_lock: impl Fn(&T::AccountId) -> BalanceOf<T>,
+	total_before: BalanceOf<T>,
+) -> Result<(), &'static str> {
+	ensure!(
+		Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+		"Rewards storage is not migrated"
+	);
+	ensure!(
+		total_reward_locks::<T>() == total_before,
+		"Reward locks changed during the upgrade"
+	);
+	check_reward_locks::<T>(balance_lock)
+}
@@ -26,7 +26,7 @@ use crate as pallet_rewards;
 use codec::Encode;
 use frame_support::{
 	parameter_types,
-	traits::{Everything, OnInitialize},
+	traits::{ConstU32, Everything, OnInitialize},
 };
 use frame_system::{self as system, InitKind};
 use sp_core::H256;
@@ -35,7 +35,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	Digest,
 };
-use sp_std::{cmp, collections::btree_map::BTreeMap};
+use sp_std::{cell::RefCell, cmp, collections::btree_map::BTreeMap};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -83,10 +83,11 @@ impl system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
 }
 
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
+	pub const ExistentialDeposit: Balance = 1;
 	pub const MaxLocks: u32 = 50;
 	pub const MaxReserves: u32 = 50;
 }
@@ -148,8 +149,30 @@ impl crate::GenerateRewardLocks<Test> for GenerateRewardLocks {
 	}
 }
 
+thread_local! {
+	static VALIDATORS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+pub struct ValidatorSet;
+impl ValidatorSet {
+	pub fn set(validators: Vec<u64>) {
+		VALIDATORS.with(|v| *v.borrow_mut() = validators);
+	}
+}
+impl validator_set_api::ValidatorSetApi<u64> for ValidatorSet {
+	fn validators() -> Vec<u64> {
+		VALIDATORS.with(|v| v.borrow().clone())
+	}
+}
+
 parameter_types! {
 	pub DonationDestination: u64 = 255;
+	pub const EarlyUnlockPenalty: PenaltyCurve<BlockNumber> = PenaltyCurve {
+		min: Perbill::from_percent(10),
+		max: Perbill::from_percent(50),
+		period: 100,
+	};
+	pub const MaxLockBonus: Perbill = Perbill::from_percent(20);
 	pub const LockBounds: pallet_rewards::LockBounds = pallet_rewards::LockBounds {period_max: 500, period_min: 20,
 																					divide_max: 50, divide_min: 2};
 }
@@ -161,6 +184,12 @@ impl pallet_rewards::Config for Test {
 	type GenerateRewardLocks = GenerateRewardLocks;
 	type WeightInfo = ();
 	type LockParametersBounds = LockBounds;
+	type ValidatorSet = ValidatorSet;
+	type MinerRewardsPercent = ConstU32<100>;
+	type TreasuryRewardsPercent = ConstU32<0>;
+	type EarlyUnlockPenalty = EarlyUnlockPenalty;
+	type EarlyUnlockPenaltyHandler = ();
+	type MaxLockBonus = MaxLockBonus;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -171,6 +200,7 @@ pub fn new_test_ext(author: u64) -> sp_io::TestExternalities {
 	pallet_rewards::GenesisConfig::<Test> {
 		reward: 60,
 		mints: BTreeMap::new(),
+		emission_curve: None,
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -179,7 +209,7 @@ pub fn new_test_ext(author: u64) -> sp_io::TestExternalities {
 	ext.execute_with(|| {
 		let current_block = 1;
 		let parent_hash = System::parent_hash();
-		let pre_digest = DigestItem::PreRuntime(sp_consensus_pow::POW_ENGINE_ID, author.encode());
+		let pre_digest = DigestItem::PreRuntime(POSCAN_ENGINE_ID, author.encode());
 		System::initialize(
 			&current_block,
 			&parent_hash,
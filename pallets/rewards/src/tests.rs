@@ -23,7 +23,7 @@
 use crate::mock::*;
 use crate::*;
 use frame_support::error::BadOrigin;
-use frame_support::traits::{OnFinalize, OnInitialize};
+use frame_support::traits::{GetStorageVersion, OnFinalize, OnInitialize};
 use frame_support::{assert_noop, assert_ok};
 use frame_system::InitKind;
 use pallet_balances::Error as BalancesError;
@@ -42,7 +42,7 @@ fn run_to_block(n: u64, author: u64) {
 
 		let current_block = System::block_number() + 1;
 		let parent_hash = System::parent_hash();
-		let pre_digest = DigestItem::PreRuntime(sp_consensus_pow::POW_ENGINE_ID, author.encode());
+		let pre_digest = DigestItem::PreRuntime(POSCAN_ENGINE_ID, author.encode());
 		System::initialize(
 			&current_block,
 			&parent_hash,
@@ -92,7 +92,7 @@ fn set_reward_works() {
 			Default::default()
 		));
 		assert_eq!(Reward::<Test>::get(), 42);
-		assert_eq!(last_event(), RawEvent::ScheduleSet.into());
+		assert_eq!(last_event(), crate::Event::<Test>::ScheduleSet.into());
 		// Fails when too low
 		assert_noop!(
 			Rewards::set_schedule(
@@ -169,7 +169,7 @@ fn reward_locks_work() {
 		// 10 blocks later (10 days)
 		System::set_block_number(11);
 		// User update locks
-		assert_ok!(Rewards::unlock(Origin::signed(1), 1));
+		assert_ok!(Rewards::unlock(Origin::signed(1)));
 		// Locks updated
 		expected_locks.remove(&11);
 		assert_eq!(Rewards::reward_locks(1), expected_locks);
@@ -209,11 +209,11 @@ fn reward_locks_work() {
 
 		// 20 more is unlocked on block 21
 		System::set_block_number(21);
-		assert_ok!(Rewards::unlock(Origin::signed(1), 1));
+		assert_ok!(Rewards::unlock(Origin::signed(1)));
 		assert_ok!(Balances::transfer(Origin::signed(1), 2, 20));
 		// 10 more unlocked on block 22
 		System::set_block_number(22);
-		assert_ok!(Rewards::unlock(Origin::signed(1), 1));
+		assert_ok!(Rewards::unlock(Origin::signed(1)));
 		assert_ok!(Balances::transfer(Origin::signed(1), 2, 10));
 
 		// Cannot transfer more
@@ -274,7 +274,7 @@ fn reward_locks_work() {
 
 		// 22 more is unlocked on block 31
 		System::set_block_number(31);
-		assert_ok!(Rewards::unlock(Origin::signed(1), 1));
+		assert_ok!(Rewards::unlock(Origin::signed(1)));
 		assert_ok!(Balances::transfer(Origin::signed(1), 2, 22));
 		assert_noop!(
 			Balances::transfer(Origin::signed(1), 2, 1),
@@ -335,7 +335,7 @@ fn curve_works() {
 fn set_lock_params_works() {
 	new_test_ext(1).execute_with(|| {
 		// Check initial data
-		assert_eq!(LockParams::get(), None);
+		assert_eq!(LockParams::<Test>::get(), None);
 		// Set lock params
 		assert_ok!(Rewards::set_lock_params(
 			Origin::root(),
@@ -352,7 +352,7 @@ fn set_lock_params_works() {
 			}))
 		);
 		assert_eq!(
-			LockParams::get(),
+			LockParams::<Test>::get(),
 			Some(LockParameters {
 				period: 90,
 				divide: 30
@@ -373,7 +373,7 @@ fn set_lock_params_works() {
 			}))
 		);
 		assert_eq!(
-			LockParams::get(),
+			LockParams::<Test>::get(),
 			Some(LockParameters {
 				period: 300,
 				divide: 50
@@ -391,7 +391,7 @@ fn set_lock_params_works() {
 			Error::<Test>::LockParamsOutOfBounds
 		);
 		assert_eq!(
-			LockParams::get(),
+			LockParams::<Test>::get(),
 			Some(LockParameters {
 				period: 300,
 				divide: 50
@@ -408,7 +408,7 @@ fn set_lock_params_works() {
 			Error::<Test>::LockParamsOutOfBounds
 		);
 		assert_eq!(
-			LockParams::get(),
+			LockParams::<Test>::get(),
 			Some(LockParameters {
 				period: 300,
 				divide: 50
@@ -426,7 +426,7 @@ fn set_lock_params_works() {
 			Error::<Test>::LockPeriodNotDivisible
 		);
 		assert_eq!(
-			LockParams::get(),
+			LockParams::<Test>::get(),
 			Some(LockParameters {
 				period: 300,
 				divide: 50
@@ -434,3 +434,583 @@ fn set_lock_params_works() {
 		);
 	});
 }
+
+#[test]
+fn set_reward_split_works() {
+	new_test_ext(1).execute_with(|| {
+		// Defaults come from the config
+		assert_eq!(
+			Rewards::reward_split(),
+			RewardShares {
+				miner: Perbill::one(),
+				validators: Perbill::zero(),
+				treasury: Perbill::zero(),
+			}
+		);
+
+		let split = RewardShares {
+			miner: Perbill::from_percent(60),
+			validators: Perbill::zero(),
+			treasury: Perbill::from_percent(40),
+		};
+		assert_noop!(Rewards::set_reward_split(Origin::signed(1), Some(split)), BadOrigin);
+		assert_noop!(
+			Rewards::set_reward_split(
+				Origin::root(),
+				Some(RewardShares { treasury: Perbill::from_percent(50), ..split })
+			),
+			Error::<Test>::InvalidRewardSplit
+		);
+
+		assert_ok!(Rewards::set_reward_split(Origin::root(), Some(split)));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::RewardSplitChanged(split))
+		);
+		assert_eq!(Rewards::reward_split(), split);
+
+		// Treasury gets its share of the block reward
+		Rewards::on_finalize(System::block_number());
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::TreasuryRewarded(24))
+		);
+		assert_eq!(Balances::free_balance(1), 36);
+		assert_eq!(Balances::free_balance(255), 24);
+
+		// Reset to the config defaults
+		assert_ok!(Rewards::set_reward_split(Origin::root(), None));
+		assert_eq!(RewardSplit::<Test>::get(), None);
+		assert_eq!(Rewards::reward_split().miner, Perbill::one());
+	});
+}
+
+#[test]
+fn validator_rewards_follow_participation() {
+	new_test_ext(1).execute_with(|| {
+		ValidatorSet::set(vec![2, 3, 4]);
+		assert_ok!(Rewards::set_reward_split(
+			Origin::root(),
+			Some(RewardShares {
+				miner: Perbill::from_percent(50),
+				validators: Perbill::from_percent(50),
+				treasury: Perbill::zero(),
+			})
+		));
+
+		// Validator rewards are held until the session ends
+		run_to_block(3, 1);
+		assert_eq!(Balances::free_balance(1), 60);
+		assert_eq!(Rewards::session_validator_rewards(), 60);
+		assert_eq!(Balances::free_balance(2), 0);
+
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&2, 3);
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&3, 1);
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&4, 2);
+		<Rewards as ValidatorParticipation<u64>>::note_offline(&4);
+		// Points noted after the report are ignored
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&4, 2);
+		assert_eq!(Rewards::round_points(), 4);
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(0);
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::ValidatorRewardsSettled(0, 60))
+		);
+		assert_eq!(Rewards::current_round(), 1);
+		assert_eq!(Rewards::round_rewards(0), Some((60, 4)));
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+		assert_eq!(Rewards::round_points(), 0);
+
+		// Shares are settled when validators participate again or claim
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&2, 1);
+		assert_eq!(Rewards::unclaimed_rewards(2), 45);
+		assert_eq!(Rewards::round_rewards(0), Some((15, 1)));
+		assert_eq!(
+			Rewards::validator_points(2),
+			Some(Participation { round: 1, points: 1, offline: false })
+		);
+		assert_noop!(Rewards::claim_rewards(Origin::signed(4)), Error::<Test>::NothingToClaim);
+		assert_ok!(Rewards::claim_rewards(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 15);
+		assert_eq!(Rewards::round_rewards(0), None);
+		assert_eq!(Rewards::validator_points(3), None);
+		assert_eq!(Balances::free_balance(2), 0);
+	});
+}
+
+#[test]
+fn validator_removed_during_session_is_paid() {
+	new_test_ext(1).execute_with(|| {
+		ValidatorSet::set(vec![2, 3]);
+		set_half_validator_share();
+
+		run_to_block(3, 1);
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&2, 1);
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&3, 2);
+		// 2 leaves the set before the session ends
+		ValidatorSet::set(vec![3]);
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(0);
+		assert_ok!(Rewards::claim_rewards(Origin::signed(2)));
+		assert_ok!(Rewards::claim_rewards(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+	});
+}
+
+#[test]
+fn claim_rewards_works() {
+	new_test_ext(1).execute_with(|| {
+		assert_noop!(Rewards::claim_rewards(Origin::signed(2)), Error::<Test>::NothingToClaim);
+
+		UnclaimedRewards::<Test>::insert(2, 55);
+		assert_ok!(Rewards::claim_rewards(Origin::signed(2)));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::RewardsClaimed(2, 55))
+		);
+		assert_eq!(Balances::free_balance(2), 55);
+		assert_eq!(Rewards::unclaimed_rewards(2), 0);
+		// Claimed rewards are locked as block rewards
+		assert_eq!(Rewards::reward_locks(2).values().sum::<u128>(), 50);
+		assert_noop!(Rewards::claim_rewards(Origin::signed(2)), Error::<Test>::NothingToClaim);
+	});
+}
+
+fn set_half_validator_share() {
+	assert_ok!(Rewards::set_reward_split(
+		Origin::root(),
+		Some(RewardShares {
+			miner: Perbill::from_percent(50),
+			validators: Perbill::from_percent(50),
+			treasury: Perbill::zero(),
+		})
+	));
+}
+
+#[test]
+fn no_validators_redirects_share() {
+	new_test_ext(1).execute_with(|| {
+		set_half_validator_share();
+
+		Rewards::on_finalize(System::block_number());
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::ValidatorShareRedirected(255, 30))
+		);
+		assert_eq!(Balances::free_balance(1), 30);
+		assert_eq!(Balances::free_balance(255), 30);
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+
+		// The set became empty during the session
+		SessionValidatorRewards::<Test>::put(40);
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(0);
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::ValidatorShareRedirected(255, 40))
+		);
+		assert_eq!(Balances::free_balance(255), 70);
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+
+		// All validators are offline
+		ValidatorSet::set(vec![2]);
+		SessionValidatorRewards::<Test>::put(10);
+		<Rewards as ValidatorParticipation<u64>>::note_offline(&2);
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(1);
+		assert_eq!(Balances::free_balance(255), 80);
+		assert_eq!(Rewards::unclaimed_rewards(2), 0);
+	});
+}
+
+#[test]
+fn one_validator_gets_whole_share() {
+	new_test_ext(1).execute_with(|| {
+		ValidatorSet::set(vec![2]);
+		set_half_validator_share();
+
+		run_to_block(3, 1);
+		<Rewards as ValidatorParticipation<u64>>::note_participation(&2, 1);
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(0);
+		assert_ok!(Rewards::claim_rewards(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(1), 60);
+		assert_eq!(Balances::free_balance(2), 60);
+		assert_eq!(Rewards::round_rewards(0), None);
+		assert_eq!(Balances::free_balance(255), 0);
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+	});
+}
+
+#[test]
+fn many_validators_share_the_whole_round() {
+	new_test_ext(1).execute_with(|| {
+		ValidatorSet::set((2..9).collect());
+		set_half_validator_share();
+
+		run_to_block(2, 1);
+		for val in 2..9 {
+			<Rewards as ValidatorParticipation<u64>>::note_participation(&val, 1);
+		}
+		<Rewards as ValidatorParticipation<u64>>::on_session_end(0);
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::ValidatorRewardsSettled(0, 30))
+		);
+		for val in 2..9 {
+			assert_ok!(Rewards::claim_rewards(Origin::signed(val)));
+			assert!((4..=5).contains(&Balances::free_balance(val)));
+		}
+		// The last validator gets the rounding leftovers
+		assert_eq!((2..9).map(Balances::free_balance).sum::<u128>(), 30);
+		assert_eq!(Rewards::round_rewards(0), None);
+		assert_eq!(Rewards::session_validator_rewards(), 0);
+		assert_eq!(Balances::free_balance(255), 0);
+	});
+}
+
+#[test]
+fn lock_schedule_works() {
+	new_test_ext(1).execute_with(|| {
+		assert_eq!(
+			Rewards::lock_schedule(&2, 1),
+			LockSchedule { unlocks: vec![], locked: 0, unlockable: 0, lock_params: None }
+		);
+
+		RewardLocks::<Test>::insert(
+			2,
+			vec![(5, 10), (10, 20), (20, 30)].into_iter().collect::<BTreeMap<_, _>>(),
+		);
+		assert_ok!(Rewards::set_lock_params(Origin::root(), LockParameters { period: 90, divide: 30 }));
+		assert_eq!(
+			Rewards::lock_schedule(&2, 10),
+			LockSchedule {
+				unlocks: vec![(20, 30)],
+				locked: 30,
+				unlockable: 30,
+				lock_params: Some(LockParameters { period: 90, divide: 30 }),
+			}
+		);
+	});
+}
+
+#[test]
+fn early_unlock_works() {
+	new_test_ext(1).execute_with(|| {
+		// Penalty grows with the time left, up to the curve period
+		assert_eq!(Rewards::early_unlock_penalty(0), Perbill::from_percent(10));
+		assert_eq!(Rewards::early_unlock_penalty(50), Perbill::from_percent(30));
+		assert_eq!(Rewards::early_unlock_penalty(100), Perbill::from_percent(50));
+		assert_eq!(Rewards::early_unlock_penalty(500), Perbill::from_percent(50));
+
+		let _ = Balances::deposit_creating(&2, 1000);
+		RewardLocks::<Test>::insert(
+			2,
+			vec![(1, 100), (51, 100), (201, 300)].into_iter().collect::<BTreeMap<_, _>>(),
+		);
+		assert_noop!(Rewards::early_unlock(Origin::signed(2), 401), Error::<Test>::InsufficientLocked);
+
+		// Releases the nearest locks first, the expired one is not charged
+		assert_ok!(Rewards::early_unlock(Origin::signed(2), 200));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::EarlyUnlocked(2, 200, 80))
+		);
+		assert_eq!(
+			Rewards::reward_locks(2),
+			vec![(201, 200)].into_iter().collect::<BTreeMap<_, _>>()
+		);
+		// The penalty is burnt
+		assert_eq!(Balances::free_balance(2), 920);
+		assert_eq!(Balances::total_issuance(), 920);
+		assert_noop!(
+			Balances::transfer(Origin::signed(2), 3, 721),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+	});
+}
+
+#[test]
+fn early_unlock_rejects_zero() {
+	new_test_ext(1).execute_with(|| {
+		RewardLocks::<Test>::insert(2, vec![(51, 100)].into_iter().collect::<BTreeMap<_, _>>());
+		assert_noop!(Rewards::early_unlock(Origin::signed(2), 0), Error::<Test>::ZeroUnlock);
+	});
+}
+
+#[test]
+fn early_unlock_of_all_locks_frees_the_balance() {
+	new_test_ext(1).execute_with(|| {
+		let _ = Balances::deposit_creating(&2, 1000);
+		RewardLocks::<Test>::insert(
+			2,
+			vec![(51, 100), (201, 300)].into_iter().collect::<BTreeMap<_, _>>(),
+		);
+		Rewards::do_update_reward_locks(&2, Rewards::reward_locks(2), 1);
+		assert_noop!(
+			Balances::transfer(Origin::signed(2), 3, 601),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+
+		assert_ok!(Rewards::early_unlock(Origin::signed(2), 400));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::EarlyUnlocked(2, 400, 180))
+		);
+		assert!(Rewards::reward_locks(2).is_empty());
+		assert!(Balances::locks(2).is_empty());
+		assert_eq!(Balances::free_balance(2), 820);
+		assert_ok!(Balances::transfer(Origin::signed(2), 3, 820));
+		assert_eq!(Balances::free_balance(3), 820);
+	});
+}
+
+#[test]
+fn account_lock_params_work() {
+	new_test_ext(1).execute_with(|| {
+		assert_noop!(
+			Rewards::set_account_lock_params(
+				Origin::signed(1),
+				Some(LockParameters { period: 600, divide: 10 })
+			),
+			Error::<Test>::LockParamsOutOfBounds
+		);
+		assert_noop!(
+			Rewards::set_account_lock_params(
+				Origin::signed(1),
+				Some(LockParameters { period: 100, divide: 30 })
+			),
+			Error::<Test>::LockPeriodNotDivisible
+		);
+
+		assert_ok!(Rewards::set_account_lock_params(
+			Origin::signed(1),
+			Some(LockParameters { period: 500, divide: 50 })
+		));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::AccountLockParamsChanged(
+				1,
+				Some(LockParameters { period: 500, divide: 50 })
+			))
+		);
+		assert_eq!(Rewards::lock_bonus(&LockParameters { period: 20, divide: 10 }), Perbill::zero());
+		assert_eq!(
+			Rewards::lock_bonus(&LockParameters { period: 260, divide: 10 }),
+			Perbill::from_percent(10)
+		);
+
+		// Reward gets the longest lock bonus and is locked with the account's parameters
+		Rewards::on_finalize(System::block_number());
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::LockBonusRewarded(1, 12))
+		);
+		assert_eq!(Balances::free_balance(1), 72);
+		let locks = Rewards::reward_locks(1);
+		assert_eq!(locks.len(), 50);
+		assert_eq!(locks.keys().last(), Some(&501));
+		assert_eq!(
+			Rewards::lock_schedule(&1, 1).lock_params,
+			Some(LockParameters { period: 500, divide: 50 })
+		);
+
+		// Other accounts keep the global parameters
+		assert_eq!(Rewards::effective_lock_params(&2), None);
+		assert_ok!(Rewards::set_account_lock_params(Origin::signed(1), None));
+		assert_eq!(Rewards::account_lock_params(1), None);
+	});
+}
+
+fn halving_curve() -> EmissionCurve<u64, u128> {
+	EmissionCurve {
+		initial_reward: 100,
+		era_length: 10,
+		offset: 0,
+		decay: FixedU128::from_rational(1, 2),
+		rounding: 0,
+	}
+}
+
+#[test]
+fn emission_curve_reward_works() {
+	const MILLICENTS: u128 = 10_000_000;
+	// The reward table the runtime used before the curve moved on chain
+	let curve = EmissionCurve::<u64, u128> {
+		initial_reward: 50_000_000 * MILLICENTS,
+		era_length: 243_000,
+		offset: 106_390,
+		decay: FixedU128::from_rational(5, 6),
+		rounding: 10 * MILLICENTS,
+	};
+	assert_eq!(curve.reward_at(0), 50_000_000 * MILLICENTS);
+	assert_eq!(curve.reward_at(136_609), 50_000_000 * MILLICENTS);
+	assert_eq!(curve.reward_at(136_610), 41_666_670 * MILLICENTS);
+	assert_eq!(curve.reward_at(379_610), 34_722_220 * MILLICENTS);
+	assert_eq!(curve.reward_at(15 * 243_000), 3_245_270 * MILLICENTS);
+	assert_eq!(curve.reward_at(88 * 243_000), 10 * MILLICENTS);
+	assert_eq!(curve.reward_at(89 * 243_000), 0);
+	assert_eq!(curve.reward_at(u64::MAX), 0);
+
+	assert_eq!(EmissionCurve::<u64, u128>::constant(42).reward_at(1_000_000), 42);
+	assert!(halving_curve().is_valid());
+	assert!(!EmissionCurve { era_length: 0, ..halving_curve() }.is_valid());
+	assert!(!EmissionCurve { decay: FixedU128::from_rational(3, 2), ..halving_curve() }.is_valid());
+}
+
+#[test]
+fn emission_curve_works() {
+	new_test_ext(1).execute_with(|| {
+		assert_noop!(
+			Rewards::set_emission_curve(Origin::signed(1), Some(halving_curve())),
+			BadOrigin
+		);
+		assert_noop!(
+			Rewards::set_emission_curve(
+				Origin::root(),
+				Some(EmissionCurve { era_length: 0, ..halving_curve() })
+			),
+			Error::<Test>::InvalidEmissionCurve
+		);
+
+		assert_ok!(Rewards::set_emission_curve(Origin::root(), Some(halving_curve())));
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::EmissionCurveChanged(Some(halving_curve())))
+		);
+		assert_eq!(Rewards::reward(), 100);
+
+		run_to_block(9, 1);
+		assert_eq!(Rewards::reward(), 100);
+		run_to_block(10, 1);
+		assert_eq!(Rewards::reward(), 50);
+		assert_eq!(
+			last_event(),
+			mock::Event::Rewards(crate::Event::<Test>::RewardChanged(50))
+		);
+
+		// Scheduled changes hold until the next era
+		assert_ok!(Rewards::set_schedule(
+			Origin::root(),
+			50,
+			Default::default(),
+			vec![(12, 42)],
+			Default::default()
+		));
+		run_to_block(12, 1);
+		assert_eq!(Rewards::reward(), 42);
+		run_to_block(19, 1);
+		assert_eq!(Rewards::reward(), 42);
+		run_to_block(20, 1);
+		assert_eq!(Rewards::reward(), 25);
+
+		// Without a curve the reward stays
+		assert_ok!(Rewards::set_emission_curve(Origin::root(), None));
+		assert_eq!(Rewards::emission_curve(), None);
+		run_to_block(30, 1);
+		assert_eq!(Rewards::reward(), 25);
+	});
+}
+
+#[test]
+fn init_emission_curve_runs_once() {
+	new_test_ext(1).execute_with(|| {
+		StorageVersion::new(1).put::<Rewards>();
+
+		migrations::init_emission_curve::<Test>(halving_curve());
+		assert_eq!(Rewards::emission_curve(), Some(halving_curve()));
+		assert_eq!(Rewards::on_chain_storage_version(), STORAGE_VERSION);
+
+		// A curve removed by governance is not put back
+		assert_ok!(Rewards::set_emission_curve(Origin::root(), None));
+		migrations::init_emission_curve::<Test>(halving_curve());
+		assert_eq!(Rewards::emission_curve(), None);
+	});
+}
+
+#[test]
+fn project_emission_follows_curve() {
+	new_test_ext(1).execute_with(|| {
+		let points = Rewards::project_emission(
+			&halving_curve(),
+			&Rewards::reward_split(),
+			1,
+			20,
+			10,
+			0,
+			None,
+			Perbill::zero(),
+			1000,
+		);
+		assert_eq!(
+			points.iter().map(|p| (p.block, p.reward, p.total_supply)).collect::<Vec<_>>(),
+			vec![(1, 100, 1100), (11, 50, 2000), (20, 25, 2425)]
+		);
+		assert_eq!(points[2].miner_issued, 1425);
+	});
+}
+
+#[test]
+fn project_emission_issues_the_whole_validator_share() {
+	new_test_ext(1).execute_with(|| {
+		let split = RewardShares {
+			miner: Perbill::from_percent(50),
+			validators: Perbill::from_percent(50),
+			treasury: Perbill::zero(),
+		};
+		// 50 of the reward of 100 does not divide between 3 validators
+		let points =
+			Rewards::project_emission(&halving_curve(), &split, 1, 1, 1, 3, None, Perbill::zero(), 0);
+		assert_eq!(points[0].validators_issued, 50);
+		assert_eq!(points[0].total_supply, 100);
+	});
+}
+
+#[test]
+fn project_emission_includes_lock_bonus() {
+	new_test_ext(1).execute_with(|| {
+		let lock_params = LockParameters { period: 500, divide: 10 };
+		let bonus = Rewards::lock_bonus(&lock_params);
+		assert_eq!(bonus, Perbill::from_percent(20));
+
+		let points = Rewards::project_emission(
+			&halving_curve(),
+			&Rewards::reward_split(),
+			1,
+			20,
+			10,
+			0,
+			Some(lock_params),
+			bonus,
+			1000,
+		);
+		assert_eq!(
+			points.iter().map(|p| (p.block, p.reward, p.total_supply)).collect::<Vec<_>>(),
+			vec![(1, 100, 1120), (11, 50, 2200), (20, 25, 2710)]
+		);
+		assert_eq!(points[2].miner_issued, 1710);
+
+		// Matches the rewards of an account choosing the same lock parameters
+		assert_ok!(Rewards::set_emission_curve(Origin::root(), Some(halving_curve())));
+		assert_ok!(Rewards::set_account_lock_params(Origin::signed(1), Some(lock_params)));
+		run_to_block(21, 1);
+		assert_eq!(Balances::free_balance(1), 1710);
+	});
+}
+
+#[test]
+fn check_projection_works() {
+	new_test_ext(1).execute_with(|| {
+		let valid = LockParameters { period: 100, divide: 10 };
+		assert_ok!(Rewards::check_projection(1, 100, Some(&valid), 100));
+		assert_ok!(Rewards::check_projection(5, 5, None, 0));
+		assert_eq!(Rewards::check_projection(10, 9, None, 100), Err(ProjectionError::EmptyRange));
+		assert_eq!(Rewards::check_projection(1, 102, None, 100), Err(ProjectionError::RangeTooLong));
+		assert_eq!(
+			Rewards::check_projection(1, 100, Some(&LockParameters { period: 10, divide: 2 }), 100),
+			Err(ProjectionError::InvalidLockParams)
+		);
+		assert_eq!(
+			Rewards::check_projection(1, 100, Some(&LockParameters { period: 100, divide: 7 }), 100),
+			Err(ProjectionError::InvalidLockParams)
+		);
+	});
+}
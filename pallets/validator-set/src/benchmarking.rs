@@ -0,0 +1,61 @@
+//! Benchmarking for the Validator Set pallet.
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+// Makes `who` the author of the block `d` blocks back, so that adding it as a validator walks
+// `d` blocks of `Authors`.
+fn mined_at_depth<T: Config>(who: &T::AccountId, d: u32) {
+	let now: T::BlockNumber = (d + 10).into();
+	frame_system::Pallet::<T>::set_block_number(now);
+	Authors::<T>::insert(now - d.into(), Some(who.clone()));
+}
+
+benchmarks! {
+	add_validator {
+		let d in 1 .. ValidatorSet::<T>::max_miner_depth();
+		let validator: T::AccountId = account("validator", 0, 0);
+		mined_at_depth::<T>(&validator, d);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(Validators::<T>::get().contains(&validator));
+	}
+
+	add_validator_self {
+		let d in 1 .. ValidatorSet::<T>::max_miner_depth();
+		let caller: T::AccountId = whitelisted_caller();
+		mined_at_depth::<T>(&caller, d);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Validators::<T>::get().contains(&caller));
+	}
+
+	remove_validator {
+		let validators: Vec<T::AccountId> = (0..T::MinAuthorities::get() + 1)
+			.map(|i| account("validator", i, 0))
+			.collect();
+		Validators::<T>::put(&validators);
+		let validator = validators[0].clone();
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(!Validators::<T>::get().contains(&validator));
+	}
+
+	add_validator_again {
+		let d in 1 .. ValidatorSet::<T>::max_miner_depth();
+		let caller: T::AccountId = whitelisted_caller();
+		mined_at_depth::<T>(&caller, d);
+		ApprovedValidators::<T>::mutate(|v| v.push(caller.clone()));
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(Validators::<T>::get().contains(&caller));
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
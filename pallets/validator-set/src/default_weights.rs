@@ -0,0 +1,33 @@
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+	fn add_validator(d: u32, ) -> Weight {
+		(31_500_000 as Weight)
+			.saturating_add((2_900_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn add_validator_self(d: u32, ) -> Weight {
+		(31_200_000 as Weight)
+			.saturating_add((2_900_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_validator() -> Weight {
+		(24_600_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn add_validator_again(d: u32, ) -> Weight {
+		(30_100_000 as Weight)
+			.saturating_add((2_900_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -15,6 +15,9 @@
 
 mod mock;
 mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
 
 use frame_support::{
 	ensure,
@@ -31,10 +34,17 @@ use sp_staking::offence::{Offence, OffenceError, ReportOffence};
 use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 use rewards_api::RewardLocksApi;
-use validator_set_api::ValidatorSetApi;
+use validator_set_api::{ValidatorParticipation, ValidatorSetApi};
 
 pub const LOG_TARGET: &'static str = "runtime::validator-set";
 
+pub trait WeightInfo {
+	fn add_validator(d: u32) -> Weight;
+	fn add_validator_self(d: u32) -> Weight;
+	fn remove_validator() -> Weight;
+	fn add_validator_again(d: u32) -> Weight;
+}
+
 pub type BalanceOf<T> =
 <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -69,6 +79,12 @@ pub mod pallet {
 		type MaxMinerDepth: Get<u32>;
 
 		type RewardLocksApi: RewardLocksApi<Self::AccountId, BalanceOf<Self>>;
+
+		/// Receives offline reports and session ends, e.g. to weight validator rewards.
+		type Participation: ValidatorParticipation<Self::AccountId>;
+
+		/// Weights for this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -163,6 +179,33 @@ pub mod pallet {
 				log::debug!(target: LOG_TARGET, "No authon");
 			}
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			Self::check_state()?;
+			Self::set_temp_storage(Validators::<T>::get(), "validators");
+			Self::set_temp_storage(ApprovedValidators::<T>::get(), "approved_validators");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			Self::check_state()?;
+			let validators: Vec<T::AccountId> =
+				Self::get_temp_storage("validators").ok_or("No validators stored before the upgrade")?;
+			ensure!(Validators::<T>::get() == validators, "Validators changed during the upgrade");
+			let approved: Vec<T::AccountId> = Self::get_temp_storage("approved_validators")
+				.ok_or("No approved validators stored before the upgrade")?;
+			ensure!(
+				ApprovedValidators::<T>::get() == approved,
+				"Approved validators changed during the upgrade"
+			);
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -193,31 +236,33 @@ pub mod pallet {
 		///
 		/// The origin can be configured using the `AddRemoveOrigin` type in the
 		/// host runtime. Can also be set to sudo/root.
-		#[pallet::weight(0)]
-		pub fn add_validator(origin: OriginFor<T>, validator_id: T::AccountId) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::add_validator(Pallet::<T>::max_miner_depth()))]
+		pub fn add_validator(origin: OriginFor<T>, validator_id: T::AccountId) -> DispatchResultWithPostInfo {
 			T::AddRemoveOrigin::ensure_origin(origin)?;
 
-			Self::do_add_validator(validator_id.clone())?;
+			let depth = Self::do_add_validator(validator_id.clone())?;
 			Self::approve_validator(validator_id)?;
 
-			Ok(())
+			Ok(Some(T::WeightInfo::add_validator(depth)).into())
 		}
 
-		#[pallet::weight(0)]
-		pub fn add_validator_self(origin: OriginFor<T>) -> DispatchResult {
+		/// Add the caller as a validator. The caller must have mined a block recently; how
+		/// far back is looked depends on its reward locks, see `FilterLevels`.
+		#[pallet::weight(T::WeightInfo::add_validator_self(Pallet::<T>::max_miner_depth()))]
+		pub fn add_validator_self(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let validator_id = ensure_signed(origin)?;
 
-			Self::do_add_validator(validator_id.clone())?;
+			let depth = Self::do_add_validator(validator_id.clone())?;
 			Self::approve_validator(validator_id)?;
 
-			Ok(())
+			Ok(Some(T::WeightInfo::add_validator_self(depth)).into())
 		}
 
 		/// Remove a validator.
 		///
 		/// The origin can be configured using the `AddRemoveOrigin` type in the
 		/// host runtime. Can also be set to sudo/root.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::remove_validator())]
 		pub fn remove_validator(
 			origin: OriginFor<T>,
 			validator_id: T::AccountId,
@@ -233,20 +278,20 @@ pub mod pallet {
 		/// Add an approved validator again when it comes back online.
 		///
 		/// For this call, the dispatch origin must be the validator itself.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_validator_again(Pallet::<T>::max_miner_depth()))]
 		pub fn add_validator_again(
 			origin: OriginFor<T>,
 			validator_id: T::AccountId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			ensure!(who == validator_id, Error::<T>::BadOrigin);
 
 			let approved_set: BTreeSet<_> = <ApprovedValidators<T>>::get().into_iter().collect();
 			ensure!(approved_set.contains(&validator_id), Error::<T>::ValidatorNotApproved);
 
-			Self::do_add_validator(validator_id)?;
+			let depth = Self::do_add_validator(validator_id)?;
 
-			Ok(())
+			Ok(Some(T::WeightInfo::add_validator_again(depth)).into())
 		}
 	}
 }
@@ -260,8 +305,32 @@ impl<T: Config> Pallet<T> {
 		<ApprovedValidators<T>>::put(validators);
 	}
 
-	fn do_add_validator(validator_id: T::AccountId) -> DispatchResult {
+	/// Checks that neither the validators nor the approved validators hold duplicates.
+	#[cfg(feature = "try-runtime")]
+	fn check_state() -> Result<(), &'static str> {
+		let validators = <Validators<T>>::get();
+		let validator_set: BTreeSet<_> = validators.iter().collect();
+		ensure!(validator_set.len() == validators.len(), "Duplicate validators");
+
+		let approved = <ApprovedValidators<T>>::get();
+		let approved_set: BTreeSet<_> = approved.iter().collect();
+		ensure!(approved_set.len() == approved.len(), "Duplicate approved validators");
+
+		Ok(())
+	}
+
+	/// Deepest `Authors` walk `do_add_validator` may do.
+	pub fn max_miner_depth() -> u32 {
+		T::FilterLevels::get()
+			.iter()
+			.map(|level| level.1)
+			.fold(T::MaxMinerDepth::get(), |a, b| a.max(b))
+	}
+
+	/// Returns the number of blocks of `Authors` walked.
+	fn do_add_validator(validator_id: T::AccountId) -> Result<u32, sp_runtime::DispatchError> {
 		let cur_block_number = <frame_system::Pallet<T>>::block_number();
+		let mut walked = 0u32;
 
 		let deposit = T::RewardLocksApi::locks(&validator_id);
 		log::debug!(target: LOG_TARGET, "Deposit: {:?}", deposit.encode());
@@ -291,6 +360,7 @@ impl<T: Config> Pallet<T> {
 				if block_num < 1u32.into() || n > depth {
 					break;
 				}
+				walked = n;
 				if let Some(author_id) = Authors::<T>::get(block_num) {
 					if validator_id == author_id {
 						log::debug!(target: LOG_TARGET, "Validator found as miner in block {:?}", block_num);
@@ -312,7 +382,7 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::ValidatorAdditionInitiated(validator_id.clone()));
 		log::debug!(target: LOG_TARGET, "Validator addition initiated.");
 
-		Ok(())
+		Ok(walked)
 	}
 
 	fn do_remove_validator(validator_id: T::AccountId) -> DispatchResult {
@@ -389,7 +459,9 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 		Some(Self::validators())
 	}
 
-	fn end_session(_end_index: u32) {}
+	fn end_session(end_index: u32) {
+		T::Participation::on_session_end(end_index);
+	}
 
 	fn start_session(_start_index: u32) {}
 }
@@ -447,6 +519,7 @@ impl<T: Config, O: Offence<(T::AccountId, T::AccountId)>>
 		let offenders = offence.offenders();
 
 		for (v, _) in offenders.into_iter() {
+			T::Participation::note_offline(&v);
 			Self::mark_for_removal(v);
 		}
 
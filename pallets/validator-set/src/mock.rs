@@ -63,6 +63,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		ValidatorSet: validator_set::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
 	}
@@ -169,7 +170,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -178,14 +179,44 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type Balance = u128;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = frame_support::traits::ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+pub struct MockRewardLocks;
+
+impl rewards_api::RewardLocksApi<u64, u128> for MockRewardLocks {
+	fn locks(_account_id: &u64) -> u128 {
+		0
+	}
+}
+
 parameter_types! {
 	pub const MinAuthorities: u32 = 2;
+	pub const PoscanEngineId: [u8; 4] = *b"posc";
+	pub const FilterLevels: [(u128, u32); 4] = [(100, 10), (200, 20), (300, 30), (400, 40)];
+	pub const MaxMinerDepth: u32 = 20;
 }
 
 impl validator_set::Config for Test {
 	type AddRemoveOrigin = EnsureRoot<Self::AccountId>;
 	type Event = Event;
 	type MinAuthorities = MinAuthorities;
+	type Currency = Balances;
+	type PoscanEngineId = PoscanEngineId;
+	type FilterLevels = FilterLevels;
+	type MaxMinerDepth = MaxMinerDepth;
+	type RewardLocksApi = MockRewardLocks;
+	type Participation = ();
+	type WeightInfo = ();
 }
 
 impl pallet_session::Config for Test {
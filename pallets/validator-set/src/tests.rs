@@ -57,3 +57,23 @@ fn duplicate_check() {
 		assert_noop!(ValidatorSet::add_validator(Origin::root(), 4), Error::<Test>::Duplicate);
 	});
 }
+
+#[test]
+fn add_validator_self_weight_grows_with_depth_walked() {
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(30);
+		Authors::<Test>::insert(25, Some(4));
+
+		let info = ValidatorSet::add_validator_self(Origin::signed(4)).unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::add_validator_self(5)));
+		assert!(<() as WeightInfo>::add_validator_self(5) < <() as WeightInfo>::add_validator_self(40));
+		assert_eq!(ValidatorSet::max_miner_depth(), 40);
+
+		// not mined within the depth
+		Authors::<Test>::insert(25, Some(5));
+		assert_noop!(
+			ValidatorSet::add_validator_self(Origin::signed(6)),
+			Error::<Test>::ValidatorHasNotMined
+		);
+	});
+}
@@ -24,7 +24,9 @@
 
 use sp_std::vec::Vec;
 use sp_runtime::ConsensusEngineId;
-use codec::Decode;
+use codec::{Codec, Decode, Encode};
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
 use lzss::{Lzss, SliceReader, VecWriter};
 
 /// The `ConsensusEngineId` of PoScan.
@@ -32,8 +34,16 @@ pub const POSCAN_ENGINE_ID: ConsensusEngineId = [b'p', b'o', b's', b'c'];
 pub const POSCAN_COIN_ID: u8 = 71;
 
 pub const POSCAN_ALGO_GRID2D: [u8; 16] = *b"grid2d-1.1      ";
+/// Number of hashes (rotations) the grid2d algorithm takes.
+pub const POSCAN_GRID2D_DEPTH: u32 = 8;
+/// Grid size of the grid2d algorithm.
+pub const POSCAN_GRID2D_GRID_SIZE: u32 = 66;
 pub const MAX_MINING_OBJ_LEN: usize = 100 * 1024;
 
+/// Inherent data identifier under which block import supplies the object hashes sealed in
+/// the block being imported, so that the runtime can reject objects mined before.
+pub const SEALED_OBJECT_INHERENT_IDENTIFIER: [u8; 8] = *b"p3d_seal";
+
 /// Type of seal.
 pub type Seal = Vec<u8>;
 pub type Difficulty = sp_core::U256;
@@ -105,6 +115,28 @@ impl TotalDifficulty for u128 {
 	}
 }
 
+/// Object hashing algorithm and its parameters.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AlgorithmParams {
+	/// Algorithm id, as put in the header.
+	pub alg_id: [u8; 16],
+	/// Number of hashes (rotations) taken.
+	pub depth: u32,
+	/// Grid size.
+	pub grid_size: u32,
+}
+
+impl AlgorithmParams {
+	/// Parameters of the algorithm in use.
+	pub fn current() -> Self {
+		AlgorithmParams {
+			alg_id: POSCAN_ALGO_GRID2D,
+			depth: POSCAN_GRID2D_DEPTH,
+			grid_size: POSCAN_GRID2D_GRID_SIZE,
+		}
+	}
+}
+
 sp_api::decl_runtime_apis! {
 	/// API necessary for timestamp-based difficulty adjustment algorithms.
 	pub trait TimestampApi<Moment: Decode> {
@@ -120,9 +152,36 @@ sp_api::decl_runtime_apis! {
 		fn difficulty() -> Difficulty;
 	}
 
+	/// Difficulty retarget state, for explorers and miners.
+	pub trait DifficultyWindowApi<Moment: Codec> {
+		/// Difficulty and timestamp of each block of the retarget window, from earliest to
+		/// latest. Slots not filled yet are `None`.
+		fn difficulty_window() -> Vec<Option<(Difficulty, Moment)>>;
+		/// Target block time in milliseconds.
+		fn target_block_time() -> Moment;
+	}
+
 	pub trait AlgorithmApi {
 		fn identifier() -> [u8; 8];
 	}
+
+	/// Consensus-relevant PoScan state, for miners and external tools.
+	pub trait PoscanApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Object hashing algorithm and its parameters.
+		fn algorithm() -> AlgorithmParams;
+		/// Object hashes mined in the last `count` blocks, most recent first.
+		fn recent_objects(count: u32) -> Vec<(BlockNumber, Vec<H256>)>;
+		/// Whether a block may not seal an object with this primary hash.
+		fn is_hash_used(hash: H256) -> bool;
+		/// Current block reward.
+		fn current_reward() -> Balance;
+		/// Pending reward locks of an account, by unlock block.
+		fn reward_locks(account: AccountId) -> Vec<(BlockNumber, Balance)>;
+	}
 }
 
 pub fn compress_obj(obj: &[u8]) -> Vec<u8> {
@@ -136,11 +195,16 @@ pub fn compress_obj(obj: &[u8]) -> Vec<u8> {
 }
 
 pub fn decompress_obj(obj: &[u8]) -> Vec<u8> {
+	try_decompress_obj(obj).unwrap()
+}
+
+/// Same as `decompress_obj` but returns `None` on malformed input instead of panicking.
+pub fn try_decompress_obj(obj: &[u8]) -> Option<Vec<u8>> {
 	type MyLzss = Lzss<10, 4, 0x20, { 1 << 10 }, { 2 << 10 }>;
 	let result = MyLzss::decompress(
 		SliceReader::new(obj),
 		VecWriter::with_capacity(4096),
 	);
 
-	result.unwrap()
+	result.ok()
 }
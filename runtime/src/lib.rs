@@ -10,8 +10,13 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 #[cfg(feature = "std")]
 pub mod genesis;
 mod fee;
+mod participation;
 mod weights;
 
+#[cfg(feature = "runtime-benchmarks")]
+#[macro_use]
+extern crate frame_benchmarking;
+
 use frame_support::{
 	traits::{
 		InitializeMembers, ChangeMembers, OnRuntimeUpgrade,
@@ -24,8 +29,8 @@ use pallet_contracts::{migration, DefaultContractAccessWeight};
 
 // use pallet_bounties::Bounties;
 
-use core::convert::TryInto;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H256};
 // use sp_core::u32_trait::{_1, _2, _4, _5};
@@ -33,10 +38,10 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify,
-		NumberFor, ConvertInto, OpaqueKeys,
+		NumberFor, ConvertInto,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, FixedPointNumber, FixedU128, MultiSignature,
 };
 use sp_std::{
 	cmp,
@@ -45,7 +50,9 @@ use sp_std::{
 };
 
 use sp_arithmetic::Percent;
-use sp_consensus_poscan::{DOLLARS, CENTS, MILLICENTS, MICROCENTS, DAYS, BLOCK_TIME, MINUTES, deposit};
+use sp_consensus_poscan::{
+	DOLLARS, CENTS, MILLICENTS, MICROCENTS, DAYS, BLOCK_TIME, MINUTES, DIFFICULTY_ADJUST_WINDOW, deposit,
+};
 use sp_consensus_poscan::{POSCAN_COIN_ID, POSCAN_ENGINE_ID};
 
 #[cfg(feature = "std")]
@@ -95,6 +102,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// A hash of some data used by the chain.
 pub type Hash = H256;
 
@@ -122,6 +132,14 @@ pub mod opaque {
 	impl_opaque_keys! {
 		pub struct SessionKeys {
 			pub grandpa: Grandpa,
+			pub im_online: ImOnline,
+		}
+	}
+
+	impl_opaque_keys! {
+		/// Session keys before im-online was added, see `upgrade_session_keys`.
+		pub struct OldSessionKeys {
+			pub grandpa: Grandpa,
 		}
 	}
 }
@@ -275,8 +293,7 @@ parameter_types! {
 }
 
 impl pallet_timestamp::Config for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Difficulty;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -403,44 +420,32 @@ parameter_types! {
 
 impl difficulty::Config for Runtime {
 	type TargetBlockTime = TargetBlockTime;
+	type DifficultyWindow = ConstU32<{ DIFFICULTY_ADJUST_WINDOW as u32 }>;
+	type DifficultyAdjustment = difficulty::MovingAverage;
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = crate::weights::difficulty::WeightInfo<Self>;
 }
 
 //------------------- rewards
 const TESTNET_LAST_BLOCK: u32 = 106390;
-const REWARDS_STEP: usize = 243000;
-const MAX_REWARS_IDX: usize = 90;
-const REWARDS: [u128; MAX_REWARS_IDX] = [
-	50_000_000 * MILLICENTS, 41_666_670 * MILLICENTS, 34_722_220 * MILLICENTS,
-	28_935_190 * MILLICENTS, 24_112_650 * MILLICENTS, 20_093_880 * MILLICENTS,
-	16_744_900 * MILLICENTS, 13_954_080 * MILLICENTS, 11_628_400 * MILLICENTS,
-	09_690_330 * MILLICENTS, 08_075_280 * MILLICENTS, 06_729_400 * MILLICENTS,
-	05_607_830 * MILLICENTS, 04_673_190 * MILLICENTS, 03_894_330 * MILLICENTS,
-	03_245_270 * MILLICENTS, 02_704_390 * MILLICENTS, 02_253_660 * MILLICENTS,
-	01_878_050 * MILLICENTS, 01_565_040 * MILLICENTS, 01_304_200 * MILLICENTS,
-	01_086_840 * MILLICENTS, 00_905_700 * MILLICENTS, 00_754_750 * MILLICENTS,
-	00_628_960 * MILLICENTS, 00_524_130 * MILLICENTS, 00_436_770 * MILLICENTS,
-	00_363_980 * MILLICENTS, 00_303_320 * MILLICENTS, 00_252_760 * MILLICENTS,
-	00_210_640 * MILLICENTS, 00_175_530 * MILLICENTS, 00_146_280 * MILLICENTS,
-	00_121_900 * MILLICENTS, 00_101_580 * MILLICENTS, 00_084_650 * MILLICENTS,
-	00_070_540 * MILLICENTS, 00_058_780 * MILLICENTS, 00_048_990 * MILLICENTS,
-	00_040_820 * MILLICENTS, 00_034_020 * MILLICENTS, 00_028_350 * MILLICENTS,
-	00_023_620 * MILLICENTS, 00_019_690 * MILLICENTS, 00_016_410 * MILLICENTS,
-	00_013_670 * MILLICENTS, 00_011_390 * MILLICENTS, 00_009_490 * MILLICENTS,
-	00_007_910 * MILLICENTS, 00_006_590 * MILLICENTS, 00_005_490 * MILLICENTS,
-	00_004_580 * MILLICENTS, 00_003_820 * MILLICENTS, 00_003_180 * MILLICENTS,
-	00_002_650 * MILLICENTS, 00_002_210 * MILLICENTS, 00_001_840 * MILLICENTS,
-	00_001_530 * MILLICENTS, 00_001_280 * MILLICENTS, 00_001_060 * MILLICENTS,
-	00_000_890 * MILLICENTS, 00_000_740 * MILLICENTS, 00_000_620 * MILLICENTS,
-	00_000_510 * MILLICENTS, 00_000_430 * MILLICENTS, 00_000_360 * MILLICENTS,
-	00_000_300 * MILLICENTS, 00_000_250 * MILLICENTS, 00_000_210 * MILLICENTS,
-	00_000_170 * MILLICENTS, 00_000_140 * MILLICENTS, 00_000_120 * MILLICENTS,
-	00_000_100 * MILLICENTS, 00_000_080 * MILLICENTS, 00_000_070 * MILLICENTS,
-	00_000_060 * MILLICENTS, 00_000_050 * MILLICENTS, 00_000_040 * MILLICENTS,
-	00_000_030 * MILLICENTS, 00_000_030 * MILLICENTS, 00_000_020 * MILLICENTS,
-	00_000_020 * MILLICENTS, 00_000_020 * MILLICENTS, 00_000_010 * MILLICENTS,
-	00_000_010 * MILLICENTS, 00_000_010 * MILLICENTS, 00_000_010 * MILLICENTS,
-	00_000_010 * MILLICENTS, 00_000_010 * MILLICENTS, 00_000_000 * MILLICENTS,
-];
+const REWARDS_STEP: u32 = 243000;
+
+/// Emission curve of the reward table used before the curve moved on chain: 500 coins a block
+/// decaying by a sixth every `REWARDS_STEP` blocks counted from the testnet, rounded to
+/// 10 millicents.
+pub fn emission_curve() -> rewards::EmissionCurve<BlockNumber, Balance> {
+	rewards::EmissionCurve {
+		initial_reward: 500 * DOLLARS,
+		era_length: REWARDS_STEP,
+		offset: TESTNET_LAST_BLOCK,
+		decay: FixedU128::from_rational(5, 6),
+		rounding: 10 * MILLICENTS,
+	}
+}
+
+/// Longest range `RewardsApi::emission_projection` projects over.
+pub const MAX_PROJECTION_BLOCKS: BlockNumber = 365 * DAYS;
 
 pub struct GenerateRewardLocks;
 
@@ -483,15 +488,6 @@ impl rewards::GenerateRewardLocks<Runtime> for GenerateRewardLocks {
 		// the first mined block ends.
 		cmp::max(100, u32::from(lock_bounds.period_max))
 	}
-
-	fn calc_rewards(current_block: BlockNumber) -> Balance {
-		let b: BlockNumber = REWARDS_STEP.try_into().unwrap();
-		let idx: usize = ((current_block + TESTNET_LAST_BLOCK) / b).try_into().unwrap();
-		if idx >= MAX_REWARS_IDX {
-			return 0
-		}
-		REWARDS[idx].clone()
-	}
 }
 
 parameter_types! {
@@ -499,6 +495,14 @@ parameter_types! {
 	pub const LockBounds: rewards::LockBounds = rewards::LockBounds {period_max: 500, period_min: 20,
 																	divide_max: 50, divide_min: 2};
 	pub const MinerRewardsPercent: u32 = 70;
+	pub const TreasuryRewardsPercent: u32 = 0;
+	pub const EarlyUnlockPenalty: rewards::PenaltyCurve<BlockNumber> = rewards::PenaltyCurve {
+		min: Perbill::from_percent(10),
+		max: Perbill::from_percent(50),
+		period: 100 * DAYS,
+	};
+	// No bonus for longer locks until one is agreed on.
+	pub const MaxLockBonus: Perbill = Perbill::zero();
 }
 
 impl rewards::Config for Runtime {
@@ -510,6 +514,10 @@ impl rewards::Config for Runtime {
 	type LockParametersBounds = LockBounds;
 	type ValidatorSet = ValidatorSet;
 	type MinerRewardsPercent = MinerRewardsPercent;
+	type TreasuryRewardsPercent = TreasuryRewardsPercent;
+	type EarlyUnlockPenalty = EarlyUnlockPenalty;
+	type EarlyUnlockPenaltyHandler = Treasury;
+	type MaxLockBonus = MaxLockBonus;
 }
 
 
@@ -809,6 +817,8 @@ impl pallet_validator_set::Config for Runtime {
 	type FilterLevels = FilterLevels;
 	type MaxMinerDepth = MaxMinerDepth;
 	type RewardLocksApi = Rewards;
+	type Participation = Rewards;
+	type WeightInfo = crate::weights::validator_set::WeightInfo<Self>;
 }
 
 parameter_types! {
@@ -822,7 +832,7 @@ impl pallet_session::Config for Runtime {
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type SessionManager = ValidatorSet;
-	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type SessionHandler = participation::SessionHandler;
 	type Keys = opaque::SessionKeys;
 	type WeightInfo = ();
 	type Event = Event;
@@ -883,7 +893,8 @@ parameter_types! {
 	pub const CollectionDeposit: Balance = 100 * DOLLARS;
 	pub const ItemDeposit: Balance = 1 * DOLLARS;
 	pub const KeyLimit: u32 = 32;
-	pub const ValueLimit: u32 = 256;
+	// fits the p3d hashes of a registered object, see `pallet_poscan::MaxObjectHashes`
+	pub const ValueLimit: u32 = 512;
 }
 
 impl pallet_uniques::Config for Runtime {
@@ -919,6 +930,36 @@ impl pallet_child_bounties::Config for Runtime {
 	type WeightInfo = (); // weights::pallet_child_bounties::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxPeerInHeartbeats: u32 = 10_000;
+	pub const MaxPeerDataEncodingSize: u32 = 1_000;
+}
+
+impl pallet_im_online::Config for Runtime {
+	type AuthorityId = ImOnlineId;
+	type Event = Event;
+	type ValidatorSet = ValidatorSet;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	// Heartbeats only earn participation points, see `participation::SessionHandler`.
+	// Reporting to `ValidatorSet` would remove unresponsive validators regardless of
+	// `MinAuthorities`.
+	type ReportUnresponsiveness = ();
+	type UnsignedPriority = ImOnlineUnsignedPriority;
+	type WeightInfo = ();
+	type MaxKeys = MaxAuthorities;
+	type MaxPeerInHeartbeats = MaxPeerInHeartbeats;
+	type MaxPeerDataEncodingSize = MaxPeerDataEncodingSize;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -1006,9 +1047,22 @@ impl pallet_contracts::Config for Runtime {
 	type MaxStorageKeyLen = ConstU32<128>;
 }
 
+parameter_types! {
+	pub const ObjectDepositBase: Balance = deposit(1, 0);
+	pub const ObjectDepositPerByte: Balance = deposit(0, 1);
+}
+
 impl pallet_poscan::Config for Runtime {
 	type Event = Event;
 	// type MaxBytesInHash = frame_support::traits::ConstU32<64>;
+	type MaxObjectHashes = ConstU32<16>;
+	type MaxMetadataLen = ConstU32<1024>;
+	type WeightInfo = crate::weights::poscan::WeightInfo<Self>;
+	// full objects only for `recent_objects`, primary hashes for a year
+	type MinedObjectsDepth = ConstU32<{ pallet_poscan::MAX_RECENT_OBJECTS }>;
+	type MinedHashesDepth = ConstU32<{ 365 * DAYS }>;
+	type ObjectDepositBase = ObjectDepositBase;
+	type ObjectDepositPerByte = ObjectDepositPerByte;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -1016,10 +1070,87 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+/// Amount locked on `who` for mining rewards.
+#[cfg(feature = "try-runtime")]
+fn reward_balance_lock(who: &AccountId) -> Balance {
+	Balances::locks(who)
+		.iter()
+		.find(|lock| lock.id == rewards::REWARDS_ID)
+		.map_or(0, |lock| lock.amount)
+}
+
+/// Placeholder im-online key of `who`, taken from its account, that session keys get when
+/// upgraded by `upgrade_session_keys`. No node holds it, so heartbeats need rotated keys.
+pub(crate) fn placeholder_im_online_key(who: &AccountId) -> ImOnlineId {
+	sp_core::sr25519::Public::from_raw(who.clone().into()).into()
+}
+
+/// Add the im-online key to the session keys of validators. It is a placeholder, so
+/// validators have to rotate their keys and `set_keys` before their heartbeats are accepted.
+/// Until then they get participation points without heartbeats, see `participation`.
+fn upgrade_session_keys() -> Weight {
+	// Keys in the old format don't decode as `SessionKeys`.
+	let upgraded = Session::validators()
+		.first()
+		.map_or(true, |v| pallet_session::NextKeys::<Runtime>::get(v).is_some());
+	if upgraded {
+		return RocksDbWeight::get().reads(2)
+	}
+
+	let keys = pallet_session::NextKeys::<Runtime>::iter_keys().count() as Weight;
+	Session::upgrade_keys::<opaque::OldSessionKeys, _>(|who, old| opaque::SessionKeys {
+		grandpa: old.grandpa,
+		im_online: placeholder_im_online_key(&who),
+	});
+	// Per validator the keys are rewritten, the owner of the old grandpa key is cleared and the
+	// owners of both new keys are put. The queued keys are rewritten once.
+	RocksDbWeight::get().reads_writes(3 + keys, 1 + 4 * keys)
+}
+
 pub struct Migrations;
 impl OnRuntimeUpgrade for Migrations {
 	fn on_runtime_upgrade() -> Weight {
-		migration::migrate::<Runtime>()
+		upgrade_session_keys()
+			.saturating_add(migration::migrate::<Runtime>())
+			.saturating_add(rewards::migrations::init_emission_curve::<Runtime>(emission_curve()))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let reward_locks = rewards::migrations::pre_upgrade::<Runtime>(reward_balance_lock)?;
+		Self::set_temp_storage(reward_locks, "reward_locks");
+
+		Difficulty::check_state()?;
+		Self::set_temp_storage(Difficulty::difficulty(), "difficulty");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let reward_locks: Balance = Self::get_temp_storage("reward_locks")
+			.ok_or("No reward locks stored before the upgrade")?;
+		rewards::migrations::post_upgrade::<Runtime>(reward_balance_lock, reward_locks)?;
+
+		// Governance may have removed the curve since it was set by the migration.
+		if let Some(curve) = Rewards::emission_curve() {
+			frame_support::ensure!(
+				curve.reward_at(System::block_number()) == Rewards::reward(),
+				"Emission curve does not match the reward"
+			);
+		}
+
+		Difficulty::check_state()?;
+		let difficulty: sp_consensus_poscan::Difficulty = Self::get_temp_storage("difficulty")
+			.ok_or("No difficulty stored before the upgrade")?;
+		frame_support::ensure!(
+			Difficulty::difficulty() == difficulty,
+			"Difficulty changed during the upgrade"
+		);
+		Ok(())
 	}
 }
 
@@ -1038,7 +1169,7 @@ construct_runtime!(
 		Indices: pallet_indices,
 		Rewards: rewards::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Balances: pallet_balances,
-		Difficulty: difficulty::{Pallet, Call, Storage, Config} ,
+		Difficulty: difficulty::{Pallet, Call, Storage, Config, Event<T>} ,
 		Authorship: pallet_authorship,
 		TransactionPayment: pallet_transaction_payment,
 		Democracy: pallet_democracy,
@@ -1060,8 +1191,10 @@ construct_runtime!(
 		Vesting: pallet_vesting,
 		Whitelist: pallet_whitelist,
 		Contracts: pallet_contracts,
-		PoScan: pallet_poscan::{Pallet, Call, Storage, Event<T>},
+		PoScan: pallet_poscan::{Pallet, Call, Storage, Event<T>, Inherent},
 		Sudo: pallet_sudo,
+		// Appended to keep the indices of the pallets above.
+		ImOnline: pallet_im_online,
 	}
 );
 
@@ -1098,6 +1231,20 @@ pub type Executive = frame_executive::Executive<
 	Migrations,
 >;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benches {
+	define_benchmarks!(
+		[frame_benchmarking, BaselineBench::<Runtime>]
+		[frame_system, SystemBench::<Runtime>]
+		[pallet_balances, Balances]
+		[pallet_timestamp, Timestamp]
+		[difficulty, Difficulty]
+		[rewards, Rewards]
+		[pallet_poscan, PoScan]
+		[pallet_validator_set, ValidatorSet]
+	);
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1267,7 +1414,134 @@ impl_runtime_apis! {
 
 	impl sp_consensus_poscan::DifficultyApi<Block, sp_consensus_poscan::Difficulty> for Runtime {
 		fn difficulty() -> sp_consensus_poscan::Difficulty {
-			difficulty::Module::<Runtime>::difficulty()
+			Difficulty::difficulty()
+		}
+	}
+
+	impl sp_consensus_poscan::DifficultyWindowApi<Block, Moment> for Runtime {
+		fn difficulty_window() -> Vec<Option<(sp_consensus_poscan::Difficulty, Moment)>> {
+			Difficulty::difficulty_window()
+				.into_iter()
+				.map(|d| d.map(|d| (d.difficulty, d.timestamp)))
+				.collect()
+		}
+
+		fn target_block_time() -> Moment {
+			Difficulty::target_block_time()
+		}
+	}
+
+	impl sp_consensus_poscan::TimestampApi<Block, Moment> for Runtime {
+		fn timestamp() -> Moment {
+			Timestamp::now()
+		}
+	}
+
+	impl sp_consensus_poscan::PoscanApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn algorithm() -> sp_consensus_poscan::AlgorithmParams {
+			sp_consensus_poscan::AlgorithmParams::current()
+		}
+
+		fn recent_objects(count: u32) -> Vec<(BlockNumber, Vec<H256>)> {
+			PoScan::recent_objects(count)
+		}
+
+		fn is_hash_used(hash: H256) -> bool {
+			PoScan::is_hash_used(&hash)
+		}
+
+		fn current_reward() -> Balance {
+			Rewards::reward()
+		}
+
+		fn reward_locks(account: AccountId) -> Vec<(BlockNumber, Balance)> {
+			Rewards::reward_locks(account).into_iter().collect()
+		}
+	}
+
+	impl rewards::LockScheduleApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn lock_schedule(account: AccountId) -> rewards::LockSchedule<BlockNumber, Balance> {
+			Rewards::lock_schedule(&account, System::block_number())
+		}
+	}
+
+	impl rewards::RewardsApi<Block, BlockNumber, Balance> for Runtime {
+		fn emission_projection(
+			from: BlockNumber,
+			to: BlockNumber,
+			step: BlockNumber,
+			validators: u32,
+			lock_params: Option<rewards::LockParameters>,
+		) -> Result<Vec<rewards::EmissionPoint<BlockNumber, Balance>>, rewards::ProjectionError> {
+			Rewards::check_projection(from, to, lock_params.as_ref(), MAX_PROJECTION_BLOCKS)?;
+			let curve = Rewards::emission_curve()
+				.unwrap_or_else(|| rewards::EmissionCurve::constant(Rewards::reward()));
+			let bonus = lock_params.as_ref().map_or(Perbill::zero(), Rewards::lock_bonus);
+			Ok(Rewards::project_emission(
+				&curve,
+				&Rewards::reward_split(),
+				from,
+				to,
+				step,
+				validators,
+				lock_params.or_else(Rewards::lock_params),
+				bonus,
+				Balances::total_issuance(),
+			))
+		}
+
+		fn emission_curve() -> Option<rewards::EmissionCurve<BlockNumber, Balance>> {
+			Rewards::emission_curve()
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn benchmark_metadata(extra: bool) -> (
+			Vec<frame_benchmarking::BenchmarkList>,
+			Vec<frame_support::traits::StorageInfo>,
+		) {
+			use frame_benchmarking::{baseline, Benchmarking, BenchmarkList};
+			use frame_support::traits::StorageInfoTrait;
+			use frame_system_benchmarking::Pallet as SystemBench;
+			use baseline::Pallet as BaselineBench;
+
+			let mut list = Vec::<BenchmarkList>::new();
+			list_benchmarks!(list, extra);
+
+			let storage_info = AllPalletsWithSystem::storage_info();
+
+			(list, storage_info)
+		}
+
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{baseline, Benchmarking, BenchmarkBatch, TrackedStorageKey};
+			use frame_system_benchmarking::Pallet as SystemBench;
+			use baseline::Pallet as BaselineBench;
+
+			impl frame_system_benchmarking::Config for Runtime {}
+			impl baseline::Config for Runtime {}
+
+			let whitelist: Vec<TrackedStorageKey> = vec![
+				// Block Number
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
+				// Total Issuance
+				hex_literal::hex!("c2261276cc9d1f8598ea4b6a74b15c2f57c875e4cff74148e4628f264b974c80").to_vec().into(),
+				// Execution Phase
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+				// Event Count
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+				// System Events
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+			];
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+			add_benchmarks!(params, batches);
+
+			Ok(batches)
 		}
 	}
 
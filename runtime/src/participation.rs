@@ -0,0 +1,145 @@
+//! Participation points of validators, earned by im-online heartbeats.
+
+use crate::{opaque, placeholder_im_online_key, AccountId, ImOnline, Rewards, Session};
+use pallet_session::SessionHandler as _;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::OpaqueKeys;
+use validator_set_api::ValidatorParticipation;
+
+type KeyHandlers = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+
+/// Session handler of the session keys that gives a participation point to every validator
+/// that was online in the ending session. im-online forgets the heartbeats of the session
+/// when it ends, so they are counted before.
+///
+/// Validators still on the placeholder im-online key of `upgrade_session_keys` can't send
+/// heartbeats, so they get a point anyway until they rotate their keys.
+pub struct SessionHandler;
+
+impl pallet_session::SessionHandler<AccountId> for SessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] =
+		<KeyHandlers as pallet_session::SessionHandler<AccountId>>::KEY_TYPE_IDS;
+
+	fn on_genesis_session<Ks: OpaqueKeys>(validators: &[(AccountId, Ks)]) {
+		KeyHandlers::on_genesis_session(validators)
+	}
+
+	fn on_new_session<Ks: OpaqueKeys>(
+		changed: bool,
+		validators: &[(AccountId, Ks)],
+		queued_validators: &[(AccountId, Ks)],
+	) {
+		KeyHandlers::on_new_session(changed, validators, queued_validators)
+	}
+
+	fn on_before_session_ending() {
+		let keys = ImOnline::keys();
+		for (index, validator) in Session::validators().iter().enumerate() {
+			let placeholder = keys.get(index) == Some(&placeholder_im_online_key(validator));
+			if placeholder || ImOnline::is_online(index as u32) {
+				Rewards::note_participation(validator, 1);
+			}
+		}
+		KeyHandlers::on_before_session_ending()
+	}
+
+	fn on_disabled(validator_index: u32) {
+		KeyHandlers::on_disabled(validator_index)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Balances, Origin, Runtime, System};
+	use codec::Encode;
+	use frame_support::{
+		assert_noop, assert_ok, sp_io::TestExternalities, traits::OneSessionHandler,
+	};
+	use pallet_im_online::Heartbeat;
+	use sp_core::{
+		offchain::{OpaqueNetworkState, OpaquePeerId},
+		sr25519, Pair,
+	};
+	use sp_consensus_poscan::DOLLARS;
+
+	fn new_test_ext() -> TestExternalities {
+		let storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		let mut ext = TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn pair(seed: &str) -> sr25519::Pair {
+		sr25519::Pair::from_string(seed, None).unwrap()
+	}
+
+	fn heartbeat(authority_index: u32, key: &sr25519::Pair) {
+		let heartbeat = Heartbeat {
+			block_number: System::block_number(),
+			network_state: OpaqueNetworkState {
+				peer_id: OpaquePeerId(vec![]),
+				external_addresses: vec![],
+			},
+			session_index: Session::current_index(),
+			authority_index,
+			validators_len: Session::validators().len() as u32,
+		};
+		let signature = key.sign(&heartbeat.encode()).into();
+		ImOnline::heartbeat(Origin::none(), heartbeat, signature).unwrap();
+	}
+
+	#[test]
+	fn heartbeats_earn_validator_rewards() {
+		new_test_ext().execute_with(|| {
+			let alice = AccountId::from(pair("//Alice").public());
+			let bob = AccountId::from(pair("//Bob").public());
+			let alice_key = pair("//Alice//im_online");
+			let bob_key = pair("//Bob//im_online");
+
+			pallet_session::Validators::<Runtime>::put(vec![alice.clone(), bob.clone()]);
+			<ImOnline as OneSessionHandler<AccountId>>::on_genesis_session(
+				vec![
+					(&alice, alice_key.public().into()),
+					(&bob, bob_key.public().into()),
+				]
+				.into_iter(),
+			);
+			heartbeat(0, &alice_key);
+
+			SessionHandler::on_before_session_ending();
+			assert_eq!(Rewards::validator_points(&alice).map(|p| p.points), Some(1));
+			assert_eq!(Rewards::validator_points(&bob), None);
+
+			rewards::SessionValidatorRewards::<Runtime>::put(1000 * DOLLARS);
+			Rewards::on_session_end(0);
+			assert_ok!(Rewards::claim_rewards(Origin::signed(alice.clone())));
+			assert_eq!(Balances::free_balance(&alice), 1000 * DOLLARS);
+			assert_noop!(
+				Rewards::claim_rewards(Origin::signed(bob)),
+				rewards::Error::<Runtime>::NothingToClaim
+			);
+		});
+	}
+
+	#[test]
+	fn placeholder_keys_earn_points_without_heartbeats() {
+		new_test_ext().execute_with(|| {
+			let alice = AccountId::from(pair("//Alice").public());
+			let bob = AccountId::from(pair("//Bob").public());
+
+			pallet_session::Validators::<Runtime>::put(vec![alice.clone(), bob.clone()]);
+			<ImOnline as OneSessionHandler<AccountId>>::on_genesis_session(
+				vec![
+					(&alice, placeholder_im_online_key(&alice)),
+					(&bob, pair("//Bob//im_online").public().into()),
+				]
+				.into_iter(),
+			);
+
+			SessionHandler::on_before_session_ending();
+			assert_eq!(Rewards::validator_points(&alice).map(|p| p.points), Some(1));
+			assert_eq!(Rewards::validator_points(&bob), None);
+		});
+	}
+}
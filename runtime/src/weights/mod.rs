@@ -20,5 +20,8 @@
 
 //! Weights for pallets used in this runtime.
 
+pub mod difficulty;
 // pub mod lockdrop;
+pub mod poscan;
 pub mod rewards;
+pub mod validator_set;
@@ -18,24 +18,15 @@
 // You should have received a copy of the GNU General Public License
 // along with 3DPass. If not, see <http://www.gnu.org/licenses/>.
 
-// Executed Command:
-// ./target/release/poscan-consensus
-// benchmark
-// --chain
-// dev
-// --steps
-// 50
-// --repeat
-// 20
-// --pallet
-// rewards
-// --extrinsic
-// *
-// --raw
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// runtime/src/weights/rewards.rs
+// on_initialize, unlock, lock, set_schedule and set_lock_params were measured with:
+// ./target/release/poscan-consensus benchmark --chain dev --steps 50 --repeat 20
+//     --pallet rewards --extrinsic '*' --raw --execution=wasm --wasm-execution=compiled
+//     --output runtime/src/weights/rewards.rs
+//
+// The storage counts of on_finalize were raised by hand for the validator share and the lock
+// bonus. set_reward_split, claim_rewards, note_participation, on_session_end, early_unlock,
+// set_account_lock_params and set_emission_curve were written by hand and have no measured
+// base time yet.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -53,8 +44,8 @@ impl<T: frame_system::Config> rewards::WeightInfo for WeightInfo<T> {
 	}
 	fn on_finalize() -> Weight {
 		(121_300_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	fn unlock() -> Weight {
 		(45_200_000 as Weight)
@@ -72,4 +63,33 @@ impl<T: frame_system::Config> rewards::WeightInfo for WeightInfo<T> {
 	fn set_lock_params() -> Weight {
 		(0 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_reward_split() -> Weight {
+		(12_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn note_participation() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn on_session_end() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn early_unlock() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_account_lock_params() -> Weight {
+		(14_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_emission_curve() -> Weight {
+		(16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
@@ -5,3 +5,20 @@ use sp_std::vec::Vec;
 pub trait ValidatorSetApi<AccountId: Clone + Ord> {
 	fn validators() -> Vec<AccountId>;
 }
+
+/// Receives validators' participation in finality, e.g. im-online heartbeats or GRANDPA votes,
+/// and the end of sessions it is accounted over.
+pub trait ValidatorParticipation<AccountId> {
+	/// `who` participated in the current session, earning `points`.
+	fn note_participation(who: &AccountId, points: u32);
+	/// `who` was reported offline or misbehaving in the current session.
+	fn note_offline(who: &AccountId);
+	/// Session `index` is ending.
+	fn on_session_end(index: u32);
+}
+
+impl<AccountId> ValidatorParticipation<AccountId> for () {
+	fn note_participation(_who: &AccountId, _points: u32) {}
+	fn note_offline(_who: &AccountId) {}
+	fn on_session_end(_index: u32) {}
+}